@@ -0,0 +1,103 @@
+// Generates one test per example fixture, see `src/solutions/examples.rs`.
+//
+// A fixture is an `examples/{day}/{part}.txt` input with the expected answer in
+// `{part}.expected` and optional `key=value` puzzle parameters in `{part}.params`.
+// Further examples of the same part are named `{part}-{n}.txt`.
+
+use std::env;
+use std::fmt::Write;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+fn main() {
+    println!("cargo:rerun-if-changed=examples");
+
+    let mut fixtures = fixtures(Path::new("examples"));
+    fixtures.sort();
+
+    let mut tests = String::new();
+    for fixture in fixtures {
+        fixture.write_test(&mut tests);
+    }
+
+    let out = PathBuf::from(env::var("OUT_DIR").unwrap()).join("examples.rs");
+    fs::write(out, tests).unwrap();
+}
+
+#[derive(PartialEq, Eq, PartialOrd, Ord)]
+struct Fixture {
+    day: u8,
+    part: u8,
+    example: u8,
+    stem: PathBuf,
+}
+
+fn fixtures(root: &Path) -> Vec<Fixture> {
+    let Ok(days) = fs::read_dir(root) else {
+        return vec![];
+    };
+
+    days.flatten()
+        .filter_map(|entry| {
+            let day = entry.file_name().to_str()?.parse().ok()?;
+            Some((day, fs::read_dir(entry.path()).ok()?))
+        })
+        .flat_map(|(day, files)| {
+            files.flatten().filter_map(move |file| {
+                let path = file.path();
+                if path.extension()? != "txt" {
+                    return None;
+                }
+
+                let stem = path.file_stem()?.to_str()?;
+                let (part, example) = match stem.split_once('-') {
+                    Some((part, example)) => (part, example.parse().ok()?),
+                    None => (stem, 1),
+                };
+
+                Some(Fixture {
+                    day,
+                    part: part.parse().ok()?,
+                    example,
+                    stem: path.with_extension(""),
+                })
+            })
+        })
+        .collect()
+}
+
+impl Fixture {
+    fn write_test(&self, out: &mut String) {
+        let input = absolute(&self.stem.with_extension("txt"));
+        let expected = absolute(&self.stem.with_extension("expected"));
+        if !expected.exists() {
+            panic!("missing the expected answer {}", expected.display());
+        }
+
+        let params: Vec<String> = fs::read_to_string(self.stem.with_extension("params"))
+            .unwrap_or_default()
+            .lines()
+            .filter(|line| !line.trim().is_empty())
+            .map(|line| format!("{:?}", line.trim()))
+            .collect();
+
+        writeln!(
+            out,
+            "#[test]\nfn day{}_part{}_example{}() {{\n    \
+             run_example({}, {}, include_str!({:?}), include_str!({:?}), &[{}]);\n}}\n",
+            self.day,
+            self.part,
+            self.example,
+            self.day,
+            self.part,
+            input,
+            expected,
+            params.join(", "),
+        )
+        .unwrap();
+    }
+}
+
+fn absolute(path: &Path) -> PathBuf {
+    Path::new(&env::var("CARGO_MANIFEST_DIR").unwrap()).join(path)
+}
@@ -0,0 +1,180 @@
+use crate::solutions::solution::{Answer, Part, Solution, SolveError};
+use serde::{Deserialize, Serialize};
+use std::fmt;
+use std::fs;
+use std::io;
+use std::path::PathBuf;
+
+const DEFAULT_ROOT: &str = "answers";
+
+/// Accepted answers of a day, stored as `answers/{day}.toml`, along with the answers the
+/// site rejected and when the last one was:
+///
+/// ```toml
+/// [part1]
+/// answer = "Fyrryn"
+///
+/// [part2]
+/// answer = 1274509803922
+///
+/// [part3]
+/// wrong = [4411, 4412]
+/// wrong_at = 1763114400
+/// ```
+#[derive(Serialize, Deserialize, Default, Debug)]
+pub struct DayAnswers {
+    pub part1: Option<PartAnswer>,
+    pub part2: Option<PartAnswer>,
+    pub part3: Option<PartAnswer>,
+}
+
+#[derive(Serialize, Deserialize, Default, Debug)]
+pub struct PartAnswer {
+    pub answer: Option<Answer>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub wrong: Vec<Answer>,
+    /// The Unix time of the last wrong submission.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub wrong_at: Option<u64>,
+}
+
+#[derive(PartialEq, Eq, Debug)]
+pub enum Verdict {
+    Match,
+    Mismatch { expected: Answer },
+    Unknown,
+}
+
+pub struct AnswerStore {
+    root: PathBuf,
+}
+
+impl AnswerStore {
+    pub fn new(root: Option<PathBuf>) -> Self {
+        Self {
+            root: root.unwrap_or_else(|| PathBuf::from(DEFAULT_ROOT)),
+        }
+    }
+
+    pub fn path(&self, day: u8) -> PathBuf {
+        self.root.join(format!("{day}.toml"))
+    }
+
+    /// Loads the answers of a day, which are empty when the file does not exist yet.
+    pub fn load(&self, day: u8) -> Result<DayAnswers, SolveError> {
+        let path = self.path(day);
+
+        let text = match fs::read_to_string(&path) {
+            Ok(text) => text,
+            Err(e) if e.kind() == io::ErrorKind::NotFound => return Ok(DayAnswers::default()),
+            Err(source) => return Err(SolveError::Io { path, source }),
+        };
+
+        toml::from_str(&text).map_err(|e| {
+            let fragment = e.span().map_or(&text[..0], |span| &text[span]);
+            SolveError::parse(fragment, e.message()).locate(&text)
+        })
+    }
+
+    pub fn save(&self, day: u8, answers: &DayAnswers) -> Result<(), SolveError> {
+        let path = self.path(day);
+        let text = toml::to_string(answers).expect("answers are serializable");
+
+        fs::create_dir_all(&self.root)
+            .and_then(|_| fs::write(&path, text))
+            .map_err(|source| SolveError::Io { path, source })
+    }
+}
+
+impl DayAnswers {
+    pub fn get(&self, part: Part) -> Option<&PartAnswer> {
+        match part {
+            Part::One => self.part1.as_ref(),
+            Part::Two => self.part2.as_ref(),
+            Part::Three => self.part3.as_ref(),
+        }
+    }
+
+    pub fn get_mut(&mut self, part: Part) -> &mut PartAnswer {
+        match part {
+            Part::One => self.part1.get_or_insert_default(),
+            Part::Two => self.part2.get_or_insert_default(),
+            Part::Three => self.part3.get_or_insert_default(),
+        }
+    }
+
+    pub fn accepted(&self, part: Part) -> Option<&Answer> {
+        self.get(part).and_then(|p| p.answer.as_ref())
+    }
+
+    /// Compares every solved part against its accepted answer.
+    pub fn check(&self, solution: &Solution) -> Vec<(Part, Verdict)> {
+        Part::ALL
+            .iter()
+            .filter_map(|part| {
+                let answer = solution.get(*part)?;
+                let verdict = match self.accepted(*part) {
+                    None => Verdict::Unknown,
+                    Some(expected) if expected == answer => Verdict::Match,
+                    Some(expected) => Verdict::Mismatch {
+                        expected: expected.clone(),
+                    },
+                };
+                Some((*part, verdict))
+            })
+            .collect()
+    }
+}
+
+impl fmt::Display for Verdict {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Verdict::Match => write!(f, "match"),
+            Verdict::Mismatch { expected } => write!(f, "mismatch (expected {expected})"),
+            Verdict::Unknown => write!(f, "unknown"),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_check() {
+        let answers: DayAnswers = toml::from_str(
+            r#"
+[part1]
+answer = 29
+
+[part2]
+answer = "781"
+
+[part3]
+answer = "[1, 2]"
+"#,
+        )
+        .unwrap();
+
+        let solution = Solution {
+            part1: Some(Answer::from(29u32)),
+            part2: Some(Answer::from(780u32)),
+            part3: Some(Answer::from((1, 2))),
+            ..Default::default()
+        };
+
+        assert_eq!(
+            answers.check(&solution),
+            vec![
+                (Part::One, Verdict::Match),
+                (
+                    Part::Two,
+                    Verdict::Mismatch {
+                        expected: Answer::Integer(781)
+                    }
+                ),
+                (Part::Three, Verdict::Match),
+            ]
+        );
+    }
+}
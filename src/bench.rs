@@ -0,0 +1,246 @@
+use crate::input::InputProvider;
+use crate::solutions::Registration;
+use crate::solutions::solution::{self, Part, PuzzleParams, SolveError};
+use argh::FromArgs;
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::path::PathBuf;
+use std::time::{Duration, Instant};
+
+#[derive(FromArgs)]
+#[argh(subcommand, name = "bench")]
+/// Benchmarks every part of the selected days
+pub struct BenchArgs {
+    /// the days to benchmark, either a single day (`3`) or an inclusive range (`1..10`), all of them when omitted
+    #[argh(option)]
+    pub day: Option<crate::Days>,
+
+    /// the number of runs of each part
+    #[argh(option, default = "10")]
+    runs: usize,
+
+    /// save the results as a JSON baseline to this file
+    #[argh(option)]
+    save: Option<PathBuf>,
+
+    /// compare the results against the JSON baseline in this file
+    #[argh(option)]
+    baseline: Option<PathBuf>,
+
+    /// the slowdown of the median solve time, in percent, reported as a regression
+    #[argh(option, default = "10.0")]
+    threshold: f64,
+}
+
+/// The slowdown of a median always put down to noise, about the cost of timing a run.
+const NOISE_FLOOR: Duration = Duration::from_micros(1);
+
+#[derive(Serialize, Deserialize, Clone, Copy, Debug)]
+pub struct Stats {
+    pub min: Duration,
+    pub median: Duration,
+    pub p95: Duration,
+}
+
+#[derive(Serialize, Deserialize, Debug)]
+pub struct BenchResult {
+    pub day: u8,
+    pub part: u8,
+    pub parse: Stats,
+    pub solve: Stats,
+}
+
+/// Runs the benchmarks and returns whether any regression against the baseline was found.
+pub fn run(
+    args: &BenchArgs,
+    registrations: &[&Registration],
+    provider: &InputProvider,
+) -> Result<bool, SolveError> {
+    if args.threshold.is_nan() || args.threshold < 0.0 {
+        return Err(SolveError::param(format!(
+            "the threshold must be a slowdown of at least 0%, got {}",
+            args.threshold
+        )));
+    }
+    let baseline = args.baseline.as_ref().map(load).transpose()?;
+    let mut results = vec![];
+    let mut regression = false;
+
+    println!("Day | Part  | Parse min/median/p95         | Solve min/median/p95");
+    for registration in registrations {
+        for part in registration.parts {
+            let Some(input) = provider.read_optional_part(registration.day, *part)? else {
+                continue;
+            };
+            let result = bench(registration, *part, &input, args.runs)?;
+
+            print!(
+                "{:<3} | {} | {:<28} | {}",
+                result.day,
+                part,
+                result.parse.to_string(),
+                result.solve
+            );
+            let previous = baseline
+                .iter()
+                .flatten()
+                .find(|b| b.day == result.day && b.part == result.part);
+            if let Some(previous) = previous {
+                print!(" | {:+.1}%", result.solve.change(&previous.solve));
+                if result.solve.regressed(&previous.solve, args.threshold) {
+                    print!(" REGRESSION");
+                    regression = true;
+                }
+            }
+            println!();
+
+            results.push(result);
+        }
+    }
+
+    if let Some(path) = &args.save {
+        let json = serde_json::to_string_pretty(&results).expect("results are serializable");
+        fs::write(path, json).map_err(|source| SolveError::Io {
+            path: path.clone(),
+            source,
+        })?;
+    }
+
+    Ok(regression)
+}
+
+/// Times `runs` runs of a part, split where the part hands back its parsed input. Parts
+/// parsing as they go, which never do, count as solving only.
+pub fn bench(
+    registration: &Registration,
+    part: Part,
+    input: &str,
+    runs: usize,
+) -> Result<BenchResult, SolveError> {
+    let solver = registration.solver;
+    let mut parse = Vec::with_capacity(runs);
+    let mut solve = Vec::with_capacity(runs);
+
+    for _ in 0..runs.max(1) {
+        solution::take_parsed();
+        let start = Instant::now();
+        solver.part(part, input, &PuzzleParams::default())?;
+        let end = Instant::now();
+        let parsed = solution::take_parsed().unwrap_or(start);
+
+        parse.push(parsed.duration_since(start));
+        solve.push(end.duration_since(parsed));
+    }
+
+    Ok(BenchResult {
+        day: registration.day,
+        part: part.number(),
+        parse: Stats::from(parse),
+        solve: Stats::from(solve),
+    })
+}
+
+fn load(path: &PathBuf) -> Result<Vec<BenchResult>, SolveError> {
+    let text = fs::read_to_string(path).map_err(|source| SolveError::Io {
+        path: path.clone(),
+        source,
+    })?;
+
+    serde_json::from_str(&text).map_err(|e| {
+        let line = text
+            .lines()
+            .nth(e.line().saturating_sub(1))
+            .unwrap_or_default();
+        SolveError::parse(line, format!("invalid baseline: {e}")).locate(&text)
+    })
+}
+
+impl Stats {
+    /// The change of the median against `previous`, in percent.
+    pub fn change(&self, previous: &Stats) -> f64 {
+        let previous = previous.median.as_secs_f64();
+        if previous == 0.0 {
+            return 0.0;
+        }
+
+        (self.median.as_secs_f64() - previous) / previous * 100.0
+    }
+
+    /// Whether the median slowed down by more than `threshold` percent against `previous`,
+    /// and by more than the spread of either runs, below which the change is noise.
+    pub fn regressed(&self, previous: &Stats, threshold: f64) -> bool {
+        let noise = [
+            self.p95.saturating_sub(self.median),
+            previous.p95.saturating_sub(previous.median),
+            NOISE_FLOOR,
+        ]
+        .into_iter()
+        .max()
+        .unwrap_or_default();
+
+        self.change(previous) > threshold && self.median.saturating_sub(previous.median) > noise
+    }
+}
+
+impl From<Vec<Duration>> for Stats {
+    fn from(mut samples: Vec<Duration>) -> Self {
+        samples.sort();
+        let percentile = |p: usize| samples[(samples.len() * p).div_ceil(100).max(1) - 1];
+
+        Self {
+            min: samples[0],
+            median: percentile(50),
+            p95: percentile(95),
+        }
+    }
+}
+
+impl std::fmt::Display for Stats {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        write!(f, "{:.2?}/{:.2?}/{:.2?}", self.min, self.median, self.p95)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_stats() {
+        let samples: Vec<Duration> = (1..=20).rev().map(Duration::from_millis).collect();
+        let stats = Stats::from(samples);
+
+        assert_eq!(stats.min, Duration::from_millis(1));
+        assert_eq!(stats.median, Duration::from_millis(10));
+        assert_eq!(stats.p95, Duration::from_millis(19));
+    }
+
+    #[test]
+    fn test_change() {
+        let stats = |ms| Stats::from(vec![Duration::from_millis(ms)]);
+
+        assert_eq!(stats(110).change(&stats(100)).round(), 10.0);
+        assert_eq!(stats(50).change(&stats(100)).round(), -50.0);
+    }
+
+    #[test]
+    fn test_regressed() {
+        let stats = |ms: &[u64]| {
+            Stats::from(
+                ms.iter()
+                    .copied()
+                    .map(Duration::from_millis)
+                    .collect::<Vec<_>>(),
+            )
+        };
+        let previous = stats(&[100, 100, 100, 101]);
+
+        assert!(stats(&[120, 120, 120, 121]).regressed(&previous, 10.0));
+        assert!(!stats(&[105, 105, 105, 106]).regressed(&previous, 10.0));
+        assert!(!stats(&[90, 120, 120, 160]).regressed(&previous, 10.0));
+        assert!(!stats(&[90, 91, 91, 92]).regressed(&previous, 0.0));
+
+        let cheap = |ns| Stats::from(vec![Duration::from_nanos(ns)]);
+        assert!(!cheap(200).regressed(&cheap(100), 10.0));
+    }
+}
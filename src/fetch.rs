@@ -0,0 +1,280 @@
+use crate::input::InputProvider;
+use crate::solutions::solution::{Part, SolveError};
+use aes::cipher::block_padding::Pkcs7;
+use aes::cipher::{BlockDecryptMut, KeyIvInit};
+use argh::FromArgs;
+use serde::Deserialize;
+use serde::de::DeserializeOwned;
+use std::cell::OnceCell;
+use std::collections::HashMap;
+use std::env;
+
+/// The session cookie of a logged in Everybody Codes account, enables fetching.
+pub const SESSION_ENV: &str = "EC_SESSION";
+pub const EVENT: u16 = 2025;
+
+pub const SITE: &str = "https://everybody.codes";
+const ASSETS: &str = "https://everybody-codes.b-cdn.net/assets";
+
+#[derive(FromArgs)]
+#[argh(subcommand, name = "fetch")]
+/// Downloads the inputs of the selected days into the inputs directory, needs $EC_SESSION
+pub struct FetchArgs {
+    /// the days to fetch, either a single day (`3`) or an inclusive range (`1..10`)
+    #[argh(option)]
+    pub day: crate::Days,
+}
+
+/// Performs the requests to the site, so tests can answer them from memory.
+pub trait Transport {
+    fn get(&self, url: &str) -> Result<String, SolveError>;
+
+    /// Posts a JSON `body`.
+    fn post(&self, url: &str, body: &str) -> Result<String, SolveError>;
+}
+
+/// Talks to the Everybody Codes site, authenticated by the session cookie.
+pub struct HttpTransport {
+    session: String,
+}
+
+/// Downloads and decrypts the notes of a quest.
+pub struct Fetcher {
+    transport: Box<dyn Transport>,
+    event: u16,
+    seed: OnceCell<u64>,
+}
+
+#[derive(Deserialize)]
+struct User {
+    seed: u64,
+}
+
+/// The decryption keys of a quest, each one is only published once its part is unlocked.
+#[derive(Deserialize)]
+struct QuestKeys {
+    key1: Option<String>,
+    key2: Option<String>,
+    key3: Option<String>,
+}
+
+/// Fetches the inputs of every selected day and prints the parts that were downloaded.
+pub fn run(args: &FetchArgs, provider: &InputProvider) -> Result<(), SolveError> {
+    for day in args.day.0.clone() {
+        let downloaded = provider.fetch(day)?;
+        let parts: Vec<String> = downloaded.iter().map(|p| p.to_string()).collect();
+
+        match parts[..] {
+            [] => println!("Day {day}: nothing new"),
+            _ => println!("Day {day}: downloaded {}", parts.join(", ")),
+        }
+    }
+
+    Ok(())
+}
+
+/// A transport authenticated by `EC_SESSION`, when it is set.
+pub fn session_transport() -> Option<Box<dyn Transport>> {
+    let session = env::var(SESSION_ENV).ok()?;
+
+    Some(Box::new(HttpTransport { session }))
+}
+
+/// A fetcher for the current event when `EC_SESSION` is set.
+pub fn from_env() -> Option<Fetcher> {
+    session_transport().map(|transport| Fetcher::new(transport, EVENT))
+}
+
+impl Fetcher {
+    pub fn new(transport: Box<dyn Transport>, event: u16) -> Self {
+        Self {
+            transport,
+            event,
+            seed: OnceCell::new(),
+        }
+    }
+
+    /// Downloads every unlocked part of a day.
+    pub fn fetch(&self, day: u8) -> Result<Vec<(Part, String)>, SolveError> {
+        let keys: QuestKeys =
+            self.get_json(&format!("{SITE}/api/event/{}/quest/{day}", self.event))?;
+        let notes: HashMap<String, String> = self.get_json(&format!(
+            "{ASSETS}/{}/{day}/input/{}.json",
+            self.event,
+            self.seed()?
+        ))?;
+
+        Part::ALL
+            .into_iter()
+            .filter_map(|part| Some((part, keys.get(part)?)))
+            .map(|(part, key)| {
+                let note = notes.get(&part.number().to_string()).ok_or_else(|| {
+                    SolveError::fetch(format!("day {day} has no notes for {part}"))
+                })?;
+                Ok((part, decrypt(key, note)?))
+            })
+            .collect()
+    }
+
+    fn seed(&self) -> Result<u64, SolveError> {
+        if let Some(seed) = self.seed.get() {
+            return Ok(*seed);
+        }
+
+        let user: User = self.get_json(&format!("{SITE}/api/user/me"))?;
+        Ok(*self.seed.get_or_init(|| user.seed))
+    }
+
+    fn get_json<T: DeserializeOwned>(&self, url: &str) -> Result<T, SolveError> {
+        let body = self.transport.get(url)?;
+
+        serde_json::from_str(&body)
+            .map_err(|e| SolveError::fetch(format!("unexpected response from {url}: {e}")))
+    }
+}
+
+/// Decrypts a hex encoded note with AES-256-CBC, the IV being the first 16 bytes of the key.
+fn decrypt(key: &str, note: &str) -> Result<String, SolveError> {
+    let key = key.as_bytes();
+    let invalid = |what: &str| SolveError::fetch(format!("could not decrypt the notes: {what}"));
+
+    let ciphertext = hex::decode(note.trim()).map_err(|_| invalid("the notes are not hex"))?;
+    let iv = key
+        .get(..16)
+        .ok_or_else(|| invalid("the key is too short"))?;
+    let plaintext = cbc::Decryptor::<aes::Aes256>::new_from_slices(key, iv)
+        .map_err(|_| invalid("the key is not 32 bytes long"))?
+        .decrypt_padded_vec_mut::<Pkcs7>(&ciphertext)
+        .map_err(|_| invalid("wrong key or padding"))?;
+
+    String::from_utf8(plaintext).map_err(|_| invalid("the notes are not UTF-8"))
+}
+
+impl QuestKeys {
+    fn get(&self, part: Part) -> Option<&str> {
+        match part {
+            Part::One => self.key1.as_deref(),
+            Part::Two => self.key2.as_deref(),
+            Part::Three => self.key3.as_deref(),
+        }
+    }
+}
+
+impl Transport for HttpTransport {
+    fn get(&self, url: &str) -> Result<String, SolveError> {
+        ureq::get(url)
+            .header("Cookie", format!("everybody-codes={}", self.session))
+            .call()
+            .and_then(|mut response| response.body_mut().read_to_string())
+            .map_err(|e| SolveError::fetch(format!("GET {url} failed: {e}")))
+    }
+
+    fn post(&self, url: &str, body: &str) -> Result<String, SolveError> {
+        ureq::post(url)
+            .header("Cookie", format!("everybody-codes={}", self.session))
+            .content_type("application/json")
+            .send(body)
+            .and_then(|mut response| response.body_mut().read_to_string())
+            .map_err(|e| SolveError::submit(format!("POST {url} failed: {e}")))
+    }
+}
+
+#[cfg(test)]
+pub mod tests {
+    use super::*;
+    use aes::cipher::BlockEncryptMut;
+    use std::cell::Cell;
+    use std::rc::Rc;
+
+    pub const KEY1: &str = "0123456789abcdef0123456789abcdef";
+
+    /// Answers from canned responses and counts the requests, POST responses are keyed by
+    /// `"{url} {body}"`.
+    #[derive(Default)]
+    pub struct MemoryTransport {
+        pub responses: HashMap<String, String>,
+        pub requests: Rc<Cell<usize>>,
+    }
+
+    impl Transport for MemoryTransport {
+        fn get(&self, url: &str) -> Result<String, SolveError> {
+            self.requests.set(self.requests.get() + 1);
+            self.responses
+                .get(url)
+                .cloned()
+                .ok_or_else(|| SolveError::fetch(format!("GET {url} failed: 404")))
+        }
+
+        fn post(&self, url: &str, body: &str) -> Result<String, SolveError> {
+            self.requests.set(self.requests.get() + 1);
+            self.responses
+                .get(&format!("{url} {body}"))
+                .cloned()
+                .ok_or_else(|| SolveError::submit(format!("POST {url} failed: 404")))
+        }
+    }
+
+    pub fn encrypt(key: &str, text: &str) -> String {
+        let key = key.as_bytes();
+        let ciphertext = cbc::Encryptor::<aes::Aes256>::new_from_slices(key, &key[..16])
+            .unwrap()
+            .encrypt_padded_vec_mut::<Pkcs7>(text.as_bytes());
+
+        hex::encode(ciphertext)
+    }
+
+    /// A transport serving day 1 to seed 7, with only part 1 unlocked.
+    pub fn day1_transport() -> MemoryTransport {
+        let responses = HashMap::from([
+            (
+                format!("{SITE}/api/user/me"),
+                r#"{"id":1,"seed":7}"#.to_string(),
+            ),
+            (
+                format!("{SITE}/api/event/2025/quest/1"),
+                format!(r#"{{"key1":"{KEY1}"}}"#),
+            ),
+            (
+                format!("{ASSETS}/2025/1/input/7.json"),
+                format!(
+                    r#"{{"1":"{}","2":"{}"}}"#,
+                    encrypt(KEY1, "Vyrdax,Drakzyph\n\nR3,L2"),
+                    encrypt("fedcba9876543210fedcba9876543210", "locked")
+                ),
+            ),
+        ]);
+
+        MemoryTransport {
+            responses,
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn test_fetch() {
+        let fetcher = Fetcher::new(Box::new(day1_transport()), EVENT);
+
+        assert_eq!(
+            fetcher.fetch(1).unwrap(),
+            vec![(Part::One, "Vyrdax,Drakzyph\n\nR3,L2".to_string())]
+        );
+        assert!(fetcher.fetch(2).is_err());
+        assert!(decrypt(KEY1, &encrypt("fedcba9876543210fedcba9876543210", "x")).is_err());
+    }
+
+    #[test]
+    fn test_post_error() {
+        let transport = HttpTransport {
+            session: "session".into(),
+        };
+        let url = "http://127.0.0.1:1/answer";
+
+        assert!(
+            transport
+                .post(url, "{}")
+                .unwrap_err()
+                .to_string()
+                .starts_with(&format!("not submitted: POST {url} failed: "))
+        );
+    }
+}
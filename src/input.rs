@@ -0,0 +1,252 @@
+use crate::fetch::Fetcher;
+use crate::solutions::solution::{Input, Part, SolveError};
+use std::env;
+use std::fs;
+use std::io::{self, Read};
+use std::path::PathBuf;
+
+pub const INPUTS_ENV: &str = "EC_INPUTS";
+const DEFAULT_ROOT: &str = "inputs";
+/// Marks a day whose last fetch left parts locked, so that reads stop asking the site.
+const LOCKED: &str = "locked";
+
+/// Loads puzzle inputs from `{root}/{day}/{part}`, which doubles as the cache of the fetcher.
+pub struct InputProvider {
+    root: PathBuf,
+    fetcher: Option<Fetcher>,
+}
+
+impl InputProvider {
+    /// Uses `root` when given, then the `EC_INPUTS` environment variable, then `inputs/`.
+    pub fn new(root: Option<PathBuf>) -> Self {
+        let root = root
+            .or_else(|| env::var_os(INPUTS_ENV).map(PathBuf::from))
+            .unwrap_or_else(|| PathBuf::from(DEFAULT_ROOT));
+
+        Self {
+            root,
+            fetcher: None,
+        }
+    }
+
+    /// Downloads the parts missing from the inputs directory with `fetcher`.
+    pub fn with_fetcher(self, fetcher: Option<Fetcher>) -> Self {
+        Self { fetcher, ..self }
+    }
+
+    pub fn dir(&self, day: u8) -> PathBuf {
+        self.root.join(day.to_string())
+    }
+
+    pub fn path(&self, day: u8, part: Part) -> PathBuf {
+        self.dir(day).join(part.number().to_string())
+    }
+
+    /// Reads a part that has to exist.
+    pub fn read_part(&self, day: u8, part: Part) -> Result<String, SolveError> {
+        let path = self.path(day, part);
+
+        fs::read_to_string(&path)
+            .map(normalize)
+            .map_err(|source| SolveError::Io { path, source })
+    }
+
+    /// Reads a part, fetching it when missing, and returns `None` when it has not been unlocked yet.
+    ///
+    /// A part that was still locked at the last fetch is not fetched again until `fetch` is
+    /// called explicitly or `unlock` records a solved part.
+    pub fn read_optional_part(&self, day: u8, part: Part) -> Result<Option<String>, SolveError> {
+        match self.read_cached_part(day, part)? {
+            None if self.fetcher.is_some() && !self.locked_path(day).exists() => {
+                self.fetch(day)?;
+                self.read_cached_part(day, part)
+            }
+            cached => Ok(cached),
+        }
+    }
+
+    /// Forgets that the day had locked parts, as solving a part unlocks the next one.
+    pub fn unlock(&self, day: u8) -> Result<(), SolveError> {
+        let path = self.locked_path(day);
+
+        match fs::remove_file(&path) {
+            Err(source) if source.kind() != io::ErrorKind::NotFound => {
+                Err(SolveError::Io { path, source })
+            }
+            _ => Ok(()),
+        }
+    }
+
+    fn locked_path(&self, day: u8) -> PathBuf {
+        self.dir(day).join(LOCKED)
+    }
+
+    /// Downloads the unlocked parts of a day that are not cached yet, and returns them.
+    pub fn fetch(&self, day: u8) -> Result<Vec<Part>, SolveError> {
+        let fetcher = self.fetcher.as_ref().ok_or_else(|| {
+            SolveError::fetch(format!(
+                "set {} to a session cookie",
+                crate::fetch::SESSION_ENV
+            ))
+        })?;
+        if Part::ALL.iter().all(|part| self.path(day, *part).exists()) {
+            return Ok(vec![]);
+        }
+
+        let mut downloaded = vec![];
+        for (part, text) in fetcher.fetch(day)? {
+            let path = self.path(day, part);
+            if path.exists() {
+                continue;
+            }
+
+            fs::create_dir_all(self.dir(day))
+                .and_then(|_| fs::write(&path, text))
+                .map_err(|source| SolveError::Io { path, source })?;
+            downloaded.push(part);
+        }
+
+        if Part::ALL.iter().all(|part| self.path(day, *part).exists()) {
+            self.unlock(day)?;
+        } else {
+            let path = self.locked_path(day);
+            fs::create_dir_all(self.dir(day))
+                .and_then(|_| fs::write(&path, ""))
+                .map_err(|source| SolveError::Io { path, source })?;
+        }
+
+        Ok(downloaded)
+    }
+
+    fn read_cached_part(&self, day: u8, part: Part) -> Result<Option<String>, SolveError> {
+        match self.read_part(day, part) {
+            Ok(text) => Ok(Some(text)),
+            Err(SolveError::Io { source, .. }) if source.kind() == io::ErrorKind::NotFound => {
+                Ok(None)
+            }
+            Err(e) => Err(e),
+        }
+    }
+
+    /// Reads every available part of a day among `parts`, the first one has to exist.
+    pub fn read_input(&self, day: u8, parts: &[Part]) -> Result<Input, SolveError> {
+        let mut input = Input::default();
+        let mut found = false;
+
+        for part in parts {
+            if let Some(text) = self.read_optional_part(day, *part)? {
+                input.set(*part, text);
+                found = true;
+            }
+        }
+
+        // Only the later parts can be locked, a day without any input is more likely a wrong
+        // inputs root, which is reported with the path of the first part.
+        if !found && let Some(first) = parts.first() {
+            self.read_part(day, *first)?;
+        }
+
+        Ok(input)
+    }
+}
+
+pub fn read_stdin() -> Result<String, SolveError> {
+    let mut text = String::new();
+
+    io::stdin()
+        .read_to_string(&mut text)
+        .map(|_| normalize(text))
+        .map_err(|source| SolveError::Io {
+            path: PathBuf::from("<stdin>"),
+            source,
+        })
+}
+
+/// Converts line endings to `\n` and strips trailing newlines, which most parsers choke on.
+pub fn normalize(text: String) -> String {
+    let text = if text.contains('\r') {
+        text.replace("\r\n", "\n")
+    } else {
+        text
+    };
+
+    text.trim_end_matches('\n').to_string()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::fetch::EVENT;
+    use crate::fetch::tests::day1_transport;
+
+    #[test]
+    fn test_normalize() {
+        assert_eq!(normalize("1,2,3\n".into()), "1,2,3");
+        assert_eq!(normalize("a\r\nb\r\n\r\n".into()), "a\nb");
+        assert_eq!(normalize("a\n\nb".into()), "a\n\nb");
+    }
+
+    #[test]
+    fn test_read_input() {
+        let root = env::temp_dir().join(format!("ec-inputs-{}", std::process::id()));
+        fs::create_dir_all(root.join("3")).unwrap();
+        fs::write(root.join("3").join("1"), "10,5,1\n").unwrap();
+
+        let input = InputProvider::new(Some(root.clone()))
+            .read_input(3, &Part::ALL)
+            .unwrap();
+        fs::remove_dir_all(&root).unwrap();
+
+        assert_eq!(input.part1.as_deref(), Some("10,5,1"));
+        assert!(input.part2.is_none());
+        assert!(input.part3.is_none());
+
+        let missing = InputProvider::new(Some(root.clone())).read_input(3, &Part::ALL);
+        assert!(
+            matches!(missing, Err(SolveError::Io { path, .. }) if path == root.join("3").join("1"))
+        );
+    }
+
+    #[test]
+    fn test_fetch_cache() {
+        let root = env::temp_dir().join(format!("ec-fetch-{}", std::process::id()));
+        let transport = day1_transport();
+        let requests = transport.requests.clone();
+        let provider = InputProvider::new(Some(root.clone()))
+            .with_fetcher(Some(Fetcher::new(Box::new(transport), EVENT)));
+
+        let first = provider.read_optional_part(1, Part::One).unwrap();
+        let after_first = requests.get();
+        let second = provider.read_optional_part(1, Part::One).unwrap();
+        let cached = fs::read_to_string(root.join("1").join("1")).unwrap();
+        fs::remove_dir_all(root).unwrap();
+
+        assert_eq!(first.as_deref(), Some("Vyrdax,Drakzyph\n\nR3,L2"));
+        assert_eq!(second, first);
+        assert_eq!(requests.get(), after_first);
+        assert_eq!(cached, "Vyrdax,Drakzyph\n\nR3,L2");
+    }
+
+    #[test]
+    fn test_fetch_locked() {
+        let root = env::temp_dir().join(format!("ec-locked-{}", std::process::id()));
+        let transport = day1_transport();
+        let requests = transport.requests.clone();
+        let provider = InputProvider::new(Some(root.clone()))
+            .with_fetcher(Some(Fetcher::new(Box::new(transport), EVENT)));
+
+        let locked = provider.read_optional_part(1, Part::Two).unwrap();
+        let after_first = requests.get();
+        let again = provider.read_input(1, &Part::ALL).unwrap();
+        let after_second = requests.get();
+        provider.unlock(1).unwrap();
+        provider.read_optional_part(1, Part::Two).unwrap();
+        let after_unlock = requests.get();
+        fs::remove_dir_all(root).unwrap();
+
+        assert!(locked.is_none());
+        assert!(again.part1.is_some() && again.part2.is_none());
+        assert_eq!(after_second, after_first);
+        assert!(after_unlock > after_second);
+    }
+}
@@ -1,47 +1,321 @@
+use answers::{AnswerStore, Verdict};
 use argh::FromArgs;
-use solutions::solution::Solver;
-use std::fs;
-use std::time::Instant;
+use input::InputProvider;
+use report::{DayRun, Format};
+use solutions::params::Param;
+use solutions::solution::{Input, Part, PuzzleParams, SolveError};
+use solutions::{Registration, parallel};
+use std::ops::RangeInclusive;
+use std::path::PathBuf;
+use std::str::FromStr;
+use std::time::{SystemTime, UNIX_EPOCH};
+mod answers;
+mod bench;
+mod fetch;
+mod input;
+mod render;
+mod report;
+mod scaffold;
 mod solutions;
+mod submit;
 
 #[derive(FromArgs)]
 /// Executes the given Everybody Codes day
 struct Args {
-    /// the day to run
+    /// the day to run, either a single day (`3`) or an inclusive range (`1..10`)
     #[argh(option)]
-    day: u8,
+    day: Option<Days>,
+
+    /// the part to run (1, 2 or 3), runs all of them when omitted
+    #[argh(option)]
+    part: Option<Part>,
+
+    /// run every implemented day
+    #[argh(switch)]
+    all: bool,
+
+    /// list the implemented days
+    #[argh(switch)]
+    list: bool,
+
+    /// directory holding the `{day}/{part}` input files, defaults to $EC_INPUTS or `inputs`
+    #[argh(option)]
+    inputs: Option<PathBuf>,
+
+    /// read the input of the selected day and part from stdin
+    #[argh(switch)]
+    stdin: bool,
+
+    /// compare the answers against the accepted ones in the answers directory
+    #[argh(switch)]
+    check: bool,
+
+    /// directory holding the `{day}.toml` accepted answers, defaults to `answers`
+    #[argh(option)]
+    answers: Option<PathBuf>,
+
+    /// override a puzzle parameter of the selected day as `key=value`, e.g. `moves=3` to run an example, can be repeated
+    #[argh(option)]
+    param: Vec<Param>,
+
+    /// the output format: text, json, csv or tsv
+    #[argh(option, default = "Format::Text")]
+    format: Format,
+
+    #[argh(subcommand)]
+    command: Option<Command>,
+}
+
+#[derive(FromArgs)]
+#[argh(subcommand)]
+enum Command {
+    Bench(bench::BenchArgs),
+    Fetch(fetch::FetchArgs),
+    Submit(submit::SubmitArgs),
+    New(scaffold::NewArgs),
+    Render(render::RenderArgs),
 }
 
+struct Days(RangeInclusive<u8>);
+
 fn main() {
     let args: Args = argh::from_env();
 
-    let input = read_input(args.day);
-
-    let solver: Box<dyn Solver> = match args.day {
-        1 => Box::new(solutions::day1::Day1Solver {}),
-        2 => Box::new(solutions::day2::Day2Solver {}),
-        3 => Box::new(solutions::day3::Day3Solver {}),
-        4 => Box::new(solutions::day4::Day4Solver {}),
-        5 => Box::new(solutions::day5::Day5Solver {}),
-        6 => Box::new(solutions::day6::Day6Solver {}),
-        7 => Box::new(solutions::day7::Day7Solver {}),
-        8 => Box::new(solutions::day8::Day8Solver {}),
-        9 => Box::new(solutions::day9::Day9Solver {}),
-        10 => Box::new(solutions::day10::Day10Solver {}),
-        _ => todo!("Unreachable"),
+    if args.list {
+        for registration in solutions::SOLVERS {
+            let parts: Vec<String> = registration.parts.iter().map(|p| p.to_string()).collect();
+            println!(
+                "Day {}: {} ({})",
+                registration.day,
+                registration.title,
+                parts.join(", ")
+            );
+        }
+        return;
+    }
+
+    let provider = InputProvider::new(args.inputs).with_fetcher(fetch::from_env());
+
+    if let Some(Command::New(new)) = &args.command {
+        match scaffold::run(new, &provider) {
+            Ok(()) => return,
+            Err(e) => exit_with(&e.to_string()),
+        }
+    }
+
+    if let Some(Command::Fetch(fetch)) = &args.command {
+        match fetch::run(fetch, &provider) {
+            Ok(()) => return,
+            Err(e) => exit_with(&e.to_string()),
+        }
+    }
+
+    if let Some(Command::Render(render)) = &args.command {
+        match render_part(render, &provider, &PuzzleParams::from(args.param.clone())) {
+            Ok(()) => return,
+            Err(e) => exit_with(&e.to_string()),
+        }
+    }
+
+    let store = AnswerStore::new(args.answers);
+
+    if let Some(Command::Submit(submit)) = &args.command {
+        match submit_part(submit, &provider, &store) {
+            Ok(()) => return,
+            Err(e) => exit_with(&e.to_string()),
+        }
+    }
+
+    if let Some(Command::Bench(bench)) = &args.command {
+        let registrations: Vec<&Registration> = match &bench.day {
+            Some(Days(days)) => days.clone().map(registration).collect(),
+            None => solutions::SOLVERS.iter().collect(),
+        };
+        match bench::run(bench, &registrations, &provider) {
+            Ok(false) => return,
+            Ok(true) => std::process::exit(1),
+            Err(e) => exit_with(&e.to_string()),
+        }
+    }
+
+    let registrations: Vec<&Registration> = match (args.all, args.day) {
+        (true, None) => solutions::SOLVERS.iter().collect(),
+        (false, Some(Days(days))) => days.map(registration).collect(),
+        (true, Some(_)) => exit_with("--all and --day cannot be used together"),
+        (false, None) => exit_with("either --day or --all is required"),
     };
-    let now = Instant::now();
-    let solution = solver.solve(input);
-    println!("{}", solution);
-    println!("Elapsed: {:.2?}", now.elapsed());
+    if args.stdin && (args.part.is_none() || registrations.len() != 1) {
+        exit_with("--stdin requires a single --day and a --part");
+    }
+    if !args.param.is_empty() && registrations.len() != 1 {
+        exit_with("--param requires a single --day");
+    }
+
+    let params = PuzzleParams::from(args.param);
+
+    // Inputs are read up front, as fetching them is not thread safe, then days run concurrently.
+    let inputs: Vec<(&Registration, Input)> = registrations
+        .into_iter()
+        .map(|registration| {
+            let input = read_input(&provider, registration, args.part, args.stdin)
+                .unwrap_or_else(|e| exit_with(&format!("Day {}: {e}", registration.day)));
+            (registration, input)
+        })
+        .collect();
+    let results = parallel::map(&inputs, |(registration, input)| {
+        run_day(registration, input, &params).and_then(|run| match args.check {
+            true => check(run, &store),
+            false => Ok(run),
+        })
+    });
+    let runs: Vec<DayRun> = inputs
+        .iter()
+        .zip(results)
+        .map(|((registration, _), result)| {
+            result.unwrap_or_else(|e| exit_with(&format!("Day {}: {e}", registration.day)))
+        })
+        .collect();
+
+    report::print(&runs, args.format);
+
+    let mismatches = runs
+        .iter()
+        .flat_map(|run| &run.verdicts)
+        .any(|(_, verdict)| matches!(verdict, Verdict::Mismatch { .. }));
+    if mismatches {
+        std::process::exit(1);
+    }
+}
+
+fn registration(day: u8) -> &'static Registration {
+    solutions::find(day).unwrap_or_else(|| {
+        exit_with(&format!(
+            "Day {day} is not implemented, available days are: {}",
+            solutions::available_days()
+        ))
+    })
+}
+
+fn read_input(
+    provider: &InputProvider,
+    registration: &Registration,
+    part: Option<Part>,
+    stdin: bool,
+) -> Result<Input, SolveError> {
+    let day = registration.day;
+
+    match part {
+        Some(part) => {
+            let mut input = Input::default();
+            let text = match stdin {
+                true => input::read_stdin()?,
+                // A part that is neither cached nor fetched reports the missing file.
+                false => match provider.read_optional_part(day, part)? {
+                    Some(text) => text,
+                    None => provider.read_part(day, part)?,
+                },
+            };
+            input.set(part, text);
+            Ok(input)
+        }
+        None => provider.read_input(day, registration.parts),
+    }
+}
+
+fn run_day(
+    registration: &Registration,
+    input: &Input,
+    params: &PuzzleParams,
+) -> Result<DayRun, SolveError> {
+    Ok(DayRun {
+        day: registration.day,
+        solution: registration.solver.solve(input, params)?,
+        verdicts: vec![],
+    })
 }
 
-fn read_input(day: u8) -> solutions::solution::Input {
-    let file_path = format!("inputs/{}/", day);
+/// Renders a part of the real input into the image given on the command line.
+fn render_part(
+    args: &render::RenderArgs,
+    provider: &InputProvider,
+    params: &PuzzleParams,
+) -> Result<(), SolveError> {
+    let (day, part) = (args.day, args.part);
+    let input = provider
+        .read_optional_part(day, part)?
+        .ok_or_else(|| SolveError::render(format!("{part} of day {day} has no input")))?;
+
+    let image = registration(day)
+        .solver
+        .render(part, &input, params)
+        .map_err(|e| e.locate(&input))?
+        .ok_or_else(|| SolveError::render(format!("{part} of day {day} has nothing to draw")))?;
+    render::write(args, &image)
+}
+
+/// Solves a part of the real input and submits the answer.
+fn submit_part(
+    args: &submit::SubmitArgs,
+    provider: &InputProvider,
+    store: &AnswerStore,
+) -> Result<(), SolveError> {
+    let (day, part) = (args.day, args.part);
+    let registration = registration(day);
+    if !registration.parts.contains(&part) {
+        return Err(SolveError::submit(format!("day {day} has no {part}")));
+    }
+    let transport = fetch::session_transport().ok_or_else(|| {
+        SolveError::submit(format!("set {} to a session cookie", fetch::SESSION_ENV))
+    })?;
+
+    let input = provider
+        .read_optional_part(day, part)?
+        .ok_or_else(|| SolveError::submit(format!("{part} of day {day} is not unlocked yet")))?;
+    let answer = registration
+        .solver
+        .part(part, &input, &PuzzleParams::default())?;
+    let now = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .unwrap_or_default()
+        .as_secs();
+
+    let outcome =
+        submit::Submitter::new(transport, fetch::EVENT).submit(store, day, part, &answer, now)?;
+    println!("{part}: {answer} is {outcome}");
+    if outcome.correct {
+        provider.unlock(day)?;
+    }
+    Ok(())
+}
+
+fn check(run: DayRun, store: &AnswerStore) -> Result<DayRun, SolveError> {
+    let verdicts = store.load(run.day)?.check(&run.solution);
+
+    Ok(DayRun { verdicts, ..run })
+}
+
+fn exit_with(message: &str) -> ! {
+    eprintln!("{message}");
+    std::process::exit(1)
+}
+
+impl FromStr for Days {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let parse = |n: &str| {
+            n.parse::<u8>()
+                .map_err(|_| format!("invalid day '{n}' in '{s}'"))
+        };
+
+        let (start, end) = match s.split_once("..") {
+            Some((start, end)) => (parse(start)?, parse(end.trim_start_matches('='))?),
+            None => (parse(s)?, parse(s)?),
+        };
 
-    solutions::solution::Input {
-        part1: fs::read_to_string(file_path.to_string() + "/1").unwrap(),
-        part2: fs::read_to_string(file_path.to_string() + "/2").unwrap(),
-        part3: fs::read_to_string(file_path.to_string() + "/3").unwrap(),
+        if start > end {
+            return Err(format!("empty day range '{s}'"));
+        }
+        Ok(Days(start..=end))
     }
 }
@@ -0,0 +1,115 @@
+use crate::solutions::grid::Grid;
+use crate::solutions::solution::{Image, Part, SolveError};
+use argh::FromArgs;
+use std::fs;
+use std::path::PathBuf;
+
+#[derive(FromArgs)]
+#[argh(subcommand, name = "render")]
+/// Renders a part of the real input as an image
+pub struct RenderArgs {
+    /// the day to render
+    #[argh(option)]
+    pub day: u8,
+
+    /// the part to render (1, 2 or 3)
+    #[argh(option)]
+    pub part: Part,
+
+    /// the image to write, a `.pgm` grayscale or `.ppm` color bitmap, or a `.svg` drawing
+    #[argh(option, short = 'o')]
+    pub output: PathBuf,
+
+    /// shade every point by its level instead of only telling the highest level apart
+    #[argh(switch)]
+    pub levels: bool,
+}
+
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+enum Format {
+    Pgm,
+    Ppm,
+}
+
+/// Encodes `image` in the format given by the extension of the output and writes it.
+pub fn write(args: &RenderArgs, image: &Image) -> Result<(), SolveError> {
+    let extension = args.output.extension().and_then(|e| e.to_str());
+    let unsupported = |expected: &str| {
+        SolveError::render(format!(
+            "unsupported image '{}', expected {expected}",
+            args.output.display()
+        ))
+    };
+
+    let bytes = match (image, extension) {
+        (Image::Levels { levels, max }, Some("pgm")) => {
+            encode(levels, *max, Format::Pgm, args.levels)
+        }
+        (Image::Levels { levels, max }, Some("ppm")) => {
+            encode(levels, *max, Format::Ppm, args.levels)
+        }
+        (Image::Levels { .. }, _) => return Err(unsupported("a .pgm or .ppm file")),
+        (Image::Svg(svg), Some("svg")) => svg.clone().into_bytes(),
+        (Image::Svg(_), _) => return Err(unsupported("a .svg file")),
+    };
+    fs::write(&args.output, bytes).map_err(|source| SolveError::Io {
+        path: args.output.clone(),
+        source,
+    })
+}
+
+/// Draws the highest level in black, and the others in white unless `shaded`, in which case
+/// they fade from white, or from blue to yellow in color, as they get closer to `max`.
+fn encode(levels: &Grid<u8>, max: u8, format: Format, shaded: bool) -> Vec<u8> {
+    let (magic, channels) = match format {
+        Format::Pgm => ("P5", 1),
+        Format::Ppm => ("P6", 3),
+    };
+    let mut bytes = format!("{magic}\n{} {}\n255\n", levels.cols(), levels.rows()).into_bytes();
+    bytes.reserve(levels.rows() * levels.cols() * channels);
+
+    for (_, level) in levels.iter() {
+        let t = match (*level >= max, shaded) {
+            (true, _) => None,
+            (false, false) => Some(0.0),
+            (false, true) => Some(*level as f64 / max.max(1) as f64),
+        };
+
+        let pixel = match (t, format) {
+            (None, _) => [0, 0, 0],
+            (Some(t), Format::Pgm) => [(255.0 * (1.0 - t)) as u8; 3],
+            (Some(_), Format::Ppm) if !shaded => [255, 255, 255],
+            (Some(t), Format::Ppm) => [
+                (255.0 * t) as u8,
+                (220.0 * t) as u8,
+                (255.0 * (1.0 - t)) as u8,
+            ],
+        };
+        bytes.extend_from_slice(&pixel[..channels]);
+    }
+
+    bytes
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_encode() {
+        let levels = Grid::from_rows(vec![vec![0, 50], vec![100, 100]]);
+
+        assert_eq!(
+            encode(&levels, 100, Format::Pgm, false),
+            b"P5\n2 2\n255\n\xff\xff\x00\x00".to_vec()
+        );
+        assert_eq!(
+            encode(&levels, 100, Format::Pgm, true),
+            b"P5\n2 2\n255\n\xff\x7f\x00\x00".to_vec()
+        );
+        assert_eq!(
+            encode(&levels, 100, Format::Ppm, true)[11..],
+            [0, 0, 255, 127, 110, 127, 0, 0, 0, 0, 0, 0]
+        );
+    }
+}
@@ -0,0 +1,248 @@
+use crate::answers::Verdict;
+use crate::solutions::solution::{Part, Solution};
+use serde::Serialize;
+use std::str::FromStr;
+use std::time::Duration;
+
+pub struct DayRun {
+    pub day: u8,
+    pub solution: Solution,
+    pub verdicts: Vec<(Part, Verdict)>,
+}
+
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum Format {
+    Text,
+    Json,
+    Csv,
+    Tsv,
+}
+
+/// A single solved part, the unit of the machine readable formats.
+#[derive(Serialize, PartialEq, Eq, Debug)]
+pub struct Record {
+    pub day: u8,
+    pub part: u8,
+    pub answer: String,
+    pub kind: &'static str,
+    pub elapsed_ns: u128,
+    pub status: &'static str,
+    pub expected: Option<String>,
+}
+
+const HEADER: [&str; 7] = [
+    "day",
+    "part",
+    "answer",
+    "kind",
+    "elapsed_ns",
+    "status",
+    "expected",
+];
+
+pub fn print(runs: &[DayRun], format: Format) {
+    match format {
+        Format::Text => match runs {
+            [run] => print_run(run),
+            _ => print_table(runs),
+        },
+        Format::Json => {
+            let json =
+                serde_json::to_string_pretty(&records(runs)).expect("records are serializable");
+            println!("{json}");
+        }
+        Format::Csv => print_separated(runs, ",", csv_escape),
+        Format::Tsv => print_separated(runs, "\t", |field| field.replace(['\t', '\n'], " ")),
+    }
+}
+
+pub fn records(runs: &[DayRun]) -> Vec<Record> {
+    runs.iter()
+        .flat_map(|run| {
+            Part::ALL.iter().filter_map(|part| {
+                let answer = run.solution.get(*part)?;
+                let verdict = run.verdict(*part);
+
+                Some(Record {
+                    day: run.day,
+                    part: part.number(),
+                    answer: answer.to_string(),
+                    kind: answer.kind(),
+                    elapsed_ns: run.solution.elapsed(*part).unwrap_or_default().as_nanos(),
+                    status: match verdict {
+                        None => "unchecked",
+                        Some(Verdict::Match) => "match",
+                        Some(Verdict::Mismatch { .. }) => "mismatch",
+                        Some(Verdict::Unknown) => "unknown",
+                    },
+                    expected: match verdict {
+                        Some(Verdict::Mismatch { expected }) => Some(expected.to_string()),
+                        _ => None,
+                    },
+                })
+            })
+        })
+        .collect()
+}
+
+fn print_run(run: &DayRun) {
+    println!("{}", run.solution);
+    for (part, verdict) in &run.verdicts {
+        println!("{part}: {verdict}");
+    }
+    println!("Elapsed: {:.2?}", run.solution.total_elapsed());
+}
+
+fn print_table(runs: &[DayRun]) {
+    let parts: Vec<Part> = Part::ALL
+        .into_iter()
+        .filter(|part| runs.iter().any(|run| run.solution.get(*part).is_some()))
+        .collect();
+
+    let mut rows = vec![
+        std::iter::once("Day".to_string())
+            .chain(parts.iter().map(|p| p.to_string()))
+            .chain(std::iter::once("Elapsed".to_string()))
+            .collect::<Vec<String>>(),
+    ];
+
+    for run in runs {
+        rows.push(
+            std::iter::once(run.day.to_string())
+                .chain(parts.iter().map(|p| run.cell(*p)))
+                .chain(std::iter::once(format!(
+                    "{:.2?}",
+                    run.solution.total_elapsed()
+                )))
+                .collect(),
+        );
+    }
+
+    let total: Duration = runs.iter().map(|r| r.solution.total_elapsed()).sum();
+    let widths: Vec<usize> = (0..rows[0].len())
+        .map(|col| rows.iter().map(|row| row[col].len()).max().unwrap_or(0))
+        .collect();
+
+    for row in rows {
+        let cells: Vec<String> = row
+            .iter()
+            .zip(&widths)
+            .map(|(cell, width)| format!("{cell:<width$}"))
+            .collect();
+        println!("{}", cells.join(" | ").trim_end());
+    }
+    println!("Total elapsed: {:.2?}", total);
+}
+
+fn print_separated(runs: &[DayRun], separator: &str, escape: fn(&str) -> String) {
+    println!("{}", HEADER.join(separator));
+
+    for record in records(runs) {
+        let fields = [
+            record.day.to_string(),
+            record.part.to_string(),
+            escape(&record.answer),
+            record.kind.to_string(),
+            record.elapsed_ns.to_string(),
+            record.status.to_string(),
+            record.expected.as_deref().map(escape).unwrap_or_default(),
+        ];
+        println!("{}", fields.join(separator));
+    }
+}
+
+fn csv_escape(field: &str) -> String {
+    if field.contains([',', '"', '\n']) {
+        format!("\"{}\"", field.replace('"', "\"\""))
+    } else {
+        field.to_string()
+    }
+}
+
+impl DayRun {
+    fn verdict(&self, part: Part) -> Option<&Verdict> {
+        self.verdicts
+            .iter()
+            .find(|(p, _)| *p == part)
+            .map(|(_, verdict)| verdict)
+    }
+
+    fn cell(&self, part: Part) -> String {
+        let Some(answer) = self.solution.get(part) else {
+            return "-".into();
+        };
+
+        match self.verdict(part) {
+            Some(Verdict::Match) => format!("{answer} (ok)"),
+            Some(Verdict::Mismatch { expected }) => format!("{answer} (expected {expected})"),
+            Some(Verdict::Unknown) => format!("{answer} (?)"),
+            None => answer.to_string(),
+        }
+    }
+}
+
+impl FromStr for Format {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "text" => Ok(Format::Text),
+            "json" => Ok(Format::Json),
+            "csv" => Ok(Format::Csv),
+            "tsv" => Ok(Format::Tsv),
+            _ => Err(format!(
+                "invalid format '{s}', expected text, json, csv or tsv"
+            )),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::solutions::solution::Answer;
+
+    #[test]
+    fn test_records() {
+        let runs = [DayRun {
+            day: 2,
+            solution: Solution {
+                part1: Some(Answer::from((357, 862))),
+                part3: Some(Answer::from(406954u64)),
+                elapsed: vec![(Part::One, Duration::from_nanos(1500))],
+                ..Default::default()
+            },
+            verdicts: vec![(
+                Part::One,
+                Verdict::Mismatch {
+                    expected: Answer::from((357, 861)),
+                },
+            )],
+        }];
+
+        assert_eq!(
+            records(&runs),
+            vec![
+                Record {
+                    day: 2,
+                    part: 1,
+                    answer: "[357,862]".into(),
+                    kind: "coordinate",
+                    elapsed_ns: 1500,
+                    status: "mismatch",
+                    expected: Some("[357,861]".into()),
+                },
+                Record {
+                    day: 2,
+                    part: 3,
+                    answer: "406954".into(),
+                    kind: "integer",
+                    elapsed_ns: 0,
+                    status: "unchecked",
+                    expected: None,
+                },
+            ]
+        );
+        assert_eq!(csv_escape("[357,862]"), "\"[357,862]\"");
+    }
+}
@@ -0,0 +1,199 @@
+use crate::input::InputProvider;
+use crate::solutions::solution::SolveError;
+use argh::FromArgs;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+const SOLUTIONS: &str = "src/solutions";
+const EXAMPLES: &str = "examples";
+
+#[derive(FromArgs)]
+#[argh(subcommand, name = "new")]
+/// Scaffolds the solver of a new day, run from the repository root
+pub struct NewArgs {
+    /// the day to create
+    #[argh(option)]
+    pub day: u8,
+
+    /// the title of the quest
+    #[argh(option, default = "String::from(\"TODO\")")]
+    pub title: String,
+}
+
+const TEMPLATE: &str = r#"use crate::solutions::Registration;
+use crate::solutions::solution::{self, Answer, Part, PuzzleParams, SolveError};
+
+pub struct Day{N}Solver;
+
+pub const REGISTRATION: Registration = Registration {
+    day: {N},
+    title: {TITLE},
+    parts: &[Part::One, Part::Two, Part::Three],
+    solver: &Day{N}Solver,
+};
+
+impl solution::Solver for Day{N}Solver {
+    fn part1(&self, input: &str, _params: &PuzzleParams) -> Result<Answer, SolveError> {
+        Ok(part1(&solution::parsed(parse(input)?)))
+    }
+
+    fn part2(&self, input: &str, _params: &PuzzleParams) -> Result<Answer, SolveError> {
+        Ok(part2(&solution::parsed(parse(input)?)))
+    }
+
+    fn part3(&self, input: &str, _params: &PuzzleParams) -> Result<Answer, SolveError> {
+        Ok(part3(&solution::parsed(parse(input)?)))
+    }
+}
+
+fn part1(_lines: &[&str]) -> Answer {
+    Answer::Unsolved
+}
+
+fn part2(_lines: &[&str]) -> Answer {
+    Answer::Unsolved
+}
+
+fn part3(_lines: &[&str]) -> Answer {
+    Answer::Unsolved
+}
+
+fn parse(input: &str) -> Result<Vec<&str>, SolveError> {
+    Ok(input.lines().collect())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse() {
+        assert_eq!(parse("a\nb").unwrap(), vec!["a", "b"]);
+    }
+}
+"#;
+
+/// Writes the module of a new day, registers it, and creates its input and example directories.
+pub fn run(args: &NewArgs, provider: &InputProvider) -> Result<(), SolveError> {
+    let day = args.day;
+    let module = Path::new(SOLUTIONS).join(format!("day{day}.rs"));
+    let registry = Path::new(SOLUTIONS).join("mod.rs");
+
+    if !registry.exists() {
+        return Err(SolveError::scaffold(format!(
+            "{} not found, run from the repository root",
+            registry.display()
+        )));
+    }
+    if module.exists() {
+        return Err(SolveError::scaffold(format!(
+            "{} already exists",
+            module.display()
+        )));
+    }
+
+    let registered = register(&read(&registry)?, day)?;
+    write(&module, &render(day, &args.title))?;
+    write(&registry, &registered)?;
+    for dir in [
+        provider.dir(day),
+        PathBuf::from(EXAMPLES).join(day.to_string()),
+    ] {
+        fs::create_dir_all(&dir).map_err(|source| SolveError::Io { path: dir, source })?;
+    }
+
+    println!("Created {}", module.display());
+    println!(
+        "Add the examples as {EXAMPLES}/{day}/{{part}}.txt with the answer in {{part}}.expected"
+    );
+    Ok(())
+}
+
+fn render(day: u8, title: &str) -> String {
+    TEMPLATE
+        .replace("{N}", &day.to_string())
+        .replace("{TITLE}", &format!("{title:?}"))
+}
+
+/// Adds `pub mod dayN;` and `dayN::REGISTRATION` to the registry, keeping both sorted
+/// the way rustfmt and `SOLVERS` expect.
+fn register(registry: &str, day: u8) -> Result<String, SolveError> {
+    let module = format!("day{day}");
+    let mut lines: Vec<String> = registry.lines().map(String::from).collect();
+
+    let mods: Vec<usize> = (0..lines.len())
+        .filter(|idx| lines[*idx].starts_with("pub mod "))
+        .collect();
+    let declared = |idx: &usize| {
+        lines[*idx]
+            .trim_start_matches("pub mod ")
+            .trim_end_matches(';')
+            .to_string()
+    };
+    if mods.iter().any(|idx| declared(idx) == module) {
+        return Err(SolveError::scaffold(format!(
+            "{module} is already declared"
+        )));
+    }
+    let at = match mods.iter().find(|idx| declared(idx) > module) {
+        Some(idx) => *idx,
+        None => {
+            mods.last()
+                .ok_or_else(|| SolveError::scaffold("no modules declared"))?
+                + 1
+        }
+    };
+    lines.insert(at, format!("pub mod {module};"));
+
+    let start = lines
+        .iter()
+        .position(|line| line.starts_with("pub const SOLVERS"))
+        .ok_or_else(|| SolveError::scaffold("SOLVERS not found"))?;
+    let end = start
+        + lines[start..]
+            .iter()
+            .position(|line| line.trim() == "];")
+            .ok_or_else(|| SolveError::scaffold("the end of SOLVERS not found"))?;
+    let at = (start + 1..end)
+        .find(|idx| {
+            let entry = lines[*idx].trim().trim_start_matches("day");
+            let registered = entry.split("::").next().and_then(|n| n.parse::<u8>().ok());
+            registered.is_some_and(|n| n > day)
+        })
+        .unwrap_or(end);
+    lines.insert(at, format!("    {module}::REGISTRATION,"));
+
+    Ok(lines.join("\n") + "\n")
+}
+
+fn read(path: &Path) -> Result<String, SolveError> {
+    fs::read_to_string(path).map_err(|source| SolveError::Io {
+        path: path.to_path_buf(),
+        source,
+    })
+}
+
+fn write(path: &Path, text: &str) -> Result<(), SolveError> {
+    fs::write(path, text).map_err(|source| SolveError::Io {
+        path: path.to_path_buf(),
+        source,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_register() {
+        let registry = "use solution::Part;\n\npub mod day1;\npub mod day10;\npub mod day2;\npub mod error;\n\npub const SOLVERS: &[Registration] = &[\n    day1::REGISTRATION,\n    day2::REGISTRATION,\n    day10::REGISTRATION,\n];\n";
+
+        assert_eq!(
+            register(registry, 3).unwrap(),
+            "use solution::Part;\n\npub mod day1;\npub mod day10;\npub mod day2;\npub mod day3;\npub mod error;\n\npub const SOLVERS: &[Registration] = &[\n    day1::REGISTRATION,\n    day2::REGISTRATION,\n    day3::REGISTRATION,\n    day10::REGISTRATION,\n];\n"
+        );
+        assert!(register(&register(registry, 11).unwrap(), 11).is_err());
+        assert!(render(11, "A \"quoted\" title").contains(r#"title: "A \"quoted\" title","#));
+        assert!(render(11, r"C:\path\").contains(r#"title: "C:\\path\\","#));
+    }
+}
@@ -0,0 +1,153 @@
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+use std::fmt;
+use std::str::FromStr;
+
+/// The answer of a part, kept typed until it is printed or compared.
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub enum Answer {
+    Integer(i64),
+    /// Only used for values that do not fit in an `i64`, see `Answer::big`.
+    BigInteger(i128),
+    Text(String),
+    Coordinate(i64, i64),
+    Unsolved,
+}
+
+impl Answer {
+    pub fn big(value: i128) -> Self {
+        match i64::try_from(value) {
+            Ok(value) => Answer::Integer(value),
+            Err(_) => Answer::BigInteger(value),
+        }
+    }
+
+    pub fn kind(&self) -> &'static str {
+        match self {
+            Answer::Integer(_) | Answer::BigInteger(_) => "integer",
+            Answer::Text(_) => "text",
+            Answer::Coordinate(_, _) => "coordinate",
+            Answer::Unsolved => "unsolved",
+        }
+    }
+}
+
+impl fmt::Display for Answer {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Answer::Integer(n) => write!(f, "{n}"),
+            Answer::BigInteger(n) => write!(f, "{n}"),
+            Answer::Text(text) => write!(f, "{text}"),
+            Answer::Coordinate(x, y) => write!(f, "[{x},{y}]"),
+            Answer::Unsolved => write!(f, "unsolved"),
+        }
+    }
+}
+
+/// Parses the canonical form back, so `"[357, 862]"` and `"0042"` compare as typed values.
+impl FromStr for Answer {
+    type Err = std::convert::Infallible;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let s = s.trim();
+
+        if let Ok(n) = s.parse::<i128>() {
+            return Ok(Answer::big(n));
+        }
+
+        let coordinate = s
+            .strip_prefix('[')
+            .and_then(|s| s.strip_suffix(']'))
+            .and_then(|s| s.split_once(','))
+            .and_then(|(x, y)| Some((x.trim().parse().ok()?, y.trim().parse().ok()?)));
+
+        Ok(match coordinate {
+            Some((x, y)) => Answer::Coordinate(x, y),
+            None => Answer::Text(s.to_string()),
+        })
+    }
+}
+
+impl PartialEq<&str> for Answer {
+    fn eq(&self, other: &&str) -> bool {
+        Ok(self) == Answer::from_str(other).as_ref()
+    }
+}
+
+macro_rules! integer_answer {
+    ($($t:ty),*) => {
+        $(
+            impl From<$t> for Answer {
+                fn from(value: $t) -> Self {
+                    Answer::big(value as i128)
+                }
+            }
+        )*
+    };
+}
+
+integer_answer!(u32, u64, i64, usize, i128);
+
+impl From<String> for Answer {
+    fn from(value: String) -> Self {
+        Answer::Text(value)
+    }
+}
+
+impl From<&str> for Answer {
+    fn from(value: &str) -> Self {
+        Answer::Text(value.to_string())
+    }
+}
+
+impl From<(i64, i64)> for Answer {
+    fn from((x, y): (i64, i64)) -> Self {
+        Answer::Coordinate(x, y)
+    }
+}
+
+impl Serialize for Answer {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        match self {
+            Answer::Integer(n) => serializer.serialize_i64(*n),
+            other => serializer.serialize_str(&other.to_string()),
+        }
+    }
+}
+
+impl<'de> Deserialize<'de> for Answer {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        #[derive(Deserialize)]
+        #[serde(untagged)]
+        enum Stored {
+            Integer(i64),
+            Text(String),
+        }
+
+        Ok(match Stored::deserialize(deserializer)? {
+            Stored::Integer(n) => Answer::Integer(n),
+            Stored::Text(text) => text.parse().unwrap_or_else(|e| match e {}),
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_canonical_form() {
+        assert_eq!(
+            Answer::from(10_000_000_000_000u64).to_string(),
+            "10000000000000"
+        );
+        assert_eq!(Answer::from(u64::MAX), Answer::BigInteger(u64::MAX as i128));
+        assert_eq!(Answer::from((357, -862)).to_string(), "[357,-862]");
+        assert_eq!(
+            Answer::from_str("[357, -862]").unwrap(),
+            Answer::from((357, -862))
+        );
+        assert_eq!(Answer::from_str("0042").unwrap(), Answer::Integer(42));
+        assert_eq!(Answer::from("Fyrryn"), "Fyrryn");
+        assert_ne!(Answer::from(29u32), "29.0");
+    }
+}
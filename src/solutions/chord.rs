@@ -0,0 +1,131 @@
+/// A chord between two nails, stored with `from <= to` whichever way it was strung.
+#[derive(Clone, Copy, PartialEq, Eq, Hash, Debug)]
+pub struct Chord {
+    pub from: u16,
+    pub to: u16,
+}
+
+impl Chord {
+    pub fn new(a: u16, b: u16) -> Self {
+        Self {
+            from: a.min(b),
+            to: a.max(b),
+        }
+    }
+
+    /// Whether the chords cross strictly inside the circle, chords sharing a nail do not.
+    pub fn crosses(&self, other: &Chord) -> bool {
+        (self.from < other.from && other.from < self.to && self.to < other.to)
+            || (other.from < self.from && self.from < other.to && other.to < self.to)
+    }
+
+    /// Whether a strike along `self` cuts `thread`, crossing it or running along it.
+    pub fn cuts(&self, thread: &Chord) -> bool {
+        self.crosses(thread) || self == thread
+    }
+}
+
+/// The number of pairs of `chords` that cross, in O(N log N).
+///
+/// Going through the chords by their first nail, a chord crosses every earlier chord which
+/// ends strictly between its nails, and whose ends are counted by a binary indexed tree.
+/// Chords sharing their first nail never cross, so each group is counted before it is added.
+pub fn knots(chords: &[Chord]) -> u64 {
+    let mut sorted = chords.to_vec();
+    sorted.sort_by_key(|chord| chord.from);
+
+    let size = sorted
+        .iter()
+        .map(|chord| chord.to as usize)
+        .max()
+        .unwrap_or(0);
+    let mut ends = Fenwick::new(size + 1);
+    let mut knots = 0;
+
+    for group in sorted.chunk_by(|a, b| a.from == b.from) {
+        for chord in group {
+            if chord.to > chord.from + 1 {
+                knots += ends.sum(chord.to as usize - 1) - ends.sum(chord.from as usize);
+            }
+        }
+        for chord in group {
+            ends.add(chord.to as usize);
+        }
+    }
+    knots
+}
+
+/// Counts of positions, with prefix sums in O(log N).
+struct Fenwick {
+    tree: Vec<u64>,
+}
+
+impl Fenwick {
+    fn new(size: usize) -> Self {
+        Self {
+            tree: vec![0; size + 1],
+        }
+    }
+
+    fn add(&mut self, position: usize) {
+        let mut idx = position + 1;
+        while idx < self.tree.len() {
+            self.tree[idx] += 1;
+            idx += idx & idx.wrapping_neg();
+        }
+    }
+
+    /// The count of positions up to `position` included.
+    fn sum(&self, position: usize) -> u64 {
+        let mut idx = position + 1;
+        let mut sum = 0;
+        while idx > 0 {
+            sum += self.tree[idx];
+            idx -= idx & idx.wrapping_neg();
+        }
+        sum
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::solutions::testing::Lcg;
+
+    #[test]
+    fn test_chord() {
+        let chord = Chord::new(5, 1);
+
+        assert_eq!(chord, Chord { from: 1, to: 5 });
+        assert!(chord.crosses(&Chord::new(3, 7)));
+        assert!(Chord::new(7, 3).crosses(&chord));
+        assert!(!chord.crosses(&Chord::new(5, 8)));
+        assert!(!chord.crosses(&Chord::new(2, 4)));
+        assert!(!chord.crosses(&chord));
+        assert!(chord.cuts(&chord));
+    }
+
+    #[test]
+    fn test_knots() {
+        let shared = [Chord::new(1, 5), Chord::new(3, 7), Chord::new(1, 3)];
+        assert_eq!(knots(&shared), 1);
+        assert_eq!(knots(&[&shared[..], &[Chord::new(6, 2)]].concat()), 4);
+        assert_eq!(
+            knots(&[Chord::new(1, 8), Chord::new(2, 7), Chord::new(3, 6)]),
+            0
+        );
+
+        let mut random = Lcg::new(11);
+        let chords: Vec<Chord> = (0..300)
+            .map(|_| Chord::new(random.below(40) as u16 + 1, random.below(40) as u16 + 1))
+            .collect();
+
+        let brute = chords
+            .iter()
+            .enumerate()
+            .map(|(idx, chord)| chords[..idx].iter().filter(|c| c.crosses(chord)).count() as u64)
+            .sum::<u64>();
+        assert_eq!(knots(&chords), brute);
+        assert_eq!(knots(&[]), 0);
+    }
+}
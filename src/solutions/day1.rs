@@ -1,16 +1,23 @@
-use crate::solutions::solution;
+use crate::solutions::Registration;
+use crate::solutions::error::parse_number;
+use crate::solutions::solution::{self, Answer, Part, PuzzleParams, SolveError};
 
 pub struct Day1Solver;
 
+pub const REGISTRATION: Registration = Registration {
+    day: 1,
+    title: "Whispers in the Shell",
+    parts: &[Part::One, Part::Two, Part::Three],
+    solver: &Day1Solver,
+};
+
 enum Rotation {
     Left(i32),
     Right(i32),
 }
 
-fn part1(input: &str) -> String {
-    let mut lines = input.split("\n\n");
-    let names: Vec<&str> = lines.next().unwrap().split(",").collect();
-    let commands: Vec<Rotation> = lines.next().unwrap().split(",").map(to_rotation).collect();
+fn part1(input: &str) -> Result<String, SolveError> {
+    let (names, commands) = solution::parsed(parse(input)?);
 
     let size = names.len() as i32;
 
@@ -19,13 +26,11 @@ fn part1(input: &str) -> String {
         Rotation::Right(v) => (pos + v).clamp(0, size - 1),
     });
 
-    names[final_idx as usize].to_string()
+    Ok(names[final_idx as usize].to_string())
 }
 
-fn part2(input: &str) -> String {
-    let mut lines = input.split("\n\n");
-    let names: Vec<&str> = lines.next().unwrap().split(",").collect();
-    let commands: Vec<Rotation> = lines.next().unwrap().split(",").map(to_rotation).collect();
+fn part2(input: &str) -> Result<String, SolveError> {
+    let (names, commands) = solution::parsed(parse(input)?);
 
     let size = names.len() as i32;
 
@@ -34,75 +39,77 @@ fn part2(input: &str) -> String {
         Rotation::Right(v) => (pos + v).rem_euclid(size),
     });
 
-    names[final_idx as usize].to_string()
+    Ok(names[final_idx as usize].to_string())
 }
 
-fn part3(input: &str) -> String {
-    let mut lines = input.split("\n\n");
-    let names: Vec<&str> = lines.next().unwrap().split(",").collect();
-    let commands: Vec<Rotation> = lines.next().unwrap().split(",").map(to_rotation).collect();
+fn part3(input: &str) -> Result<String, SolveError> {
+    let (names, commands) = solution::parsed(parse(input)?);
 
     let size = names.len() as i32;
 
     let mut indexes: Vec<usize> = (0..size as usize).collect();
 
     commands.iter().for_each(|cmd| match cmd {
-        Rotation::Left(v) => indexes.swap(0, (size - v.rem_euclid(size)) as usize),
+        Rotation::Left(v) => indexes.swap(0, (size - v).rem_euclid(size) as usize),
         Rotation::Right(v) => indexes.swap(0, v.rem_euclid(size) as usize),
     });
 
-    names[indexes[0]].to_string()
+    Ok(names[indexes[0]].to_string())
 }
 
 impl solution::Solver for Day1Solver {
-    fn solve(&self, input: solution::Input) -> solution::Solution {
-        solution::Solution {
-            part1: part1(&input.part1),
-            part2: part2(&input.part2),
-            part3: part3(&input.part3),
-        }
+    fn part1(&self, input: &str, _params: &PuzzleParams) -> Result<Answer, SolveError> {
+        part1(input).map(Answer::from)
     }
-}
 
-fn to_rotation(chars: &str) -> Rotation {
-    let c = chars.chars().next().unwrap();
-    let n: i32 = chars[1..].parse().unwrap();
+    fn part2(&self, input: &str, _params: &PuzzleParams) -> Result<Answer, SolveError> {
+        part2(input).map(Answer::from)
+    }
 
-    match c {
-        'L' => Rotation::Left(n),
-        'R' => Rotation::Right(n),
-        _ => panic!(),
+    fn part3(&self, input: &str, _params: &PuzzleParams) -> Result<Answer, SolveError> {
+        part3(input).map(Answer::from)
     }
 }
 
+fn parse(input: &str) -> Result<(Vec<&str>, Vec<Rotation>), SolveError> {
+    let (names, commands) = input.split_once("\n\n").ok_or_else(|| {
+        SolveError::parse(
+            input,
+            "expected names and commands separated by a blank line",
+        )
+    })?;
+
+    let names = names.split(",").collect();
+    let commands = commands
+        .split(",")
+        .map(to_rotation)
+        .collect::<Result<_, _>>()?;
+
+    Ok((names, commands))
+}
+
+fn to_rotation(chars: &str) -> Result<Rotation, SolveError> {
+    let rotation = match chars.chars().next() {
+        Some('L') => Rotation::Left,
+        Some('R') => Rotation::Right,
+        _ => {
+            return Err(SolveError::parse(
+                chars,
+                "expected a rotation like 'L3' or 'R3'",
+            ));
+        }
+    };
+
+    Ok(rotation(parse_number(&chars[1..])?))
+}
+
 #[cfg(test)]
 mod tests {
-    use super::solution::Solver;
     use super::*;
 
     #[test]
-    fn test_solve() {
-        let p1 = r#"Vyrdax,Drakzyph,Fyrryn,Elarzris
-
-R3,L2,R3,L1"#;
-
-        let p2 = r#"Vyrdax,Drakzyph,Fyrryn,Elarzris
-
-R3,L2,R3,L1"#;
-
-        let p3 = r#"Vyrdax,Drakzyph,Fyrryn,Elarzris
-
-R3,L2,R3,L3"#;
-
-        let input = solution::Input {
-            part1: p1.into(),
-            part2: p2.into(),
-            part3: p3.into(),
-        };
-
-        let solution = Day1Solver.solve(input);
-        assert_eq!(solution.part1, "Fyrryn");
-        assert_eq!(solution.part2, "Elarzris");
-        assert_eq!(solution.part3, "Drakzyph");
+    fn test_part3_full_turn() {
+        assert_eq!(part3("A,B,C\n\nL3,R1").unwrap(), "B");
+        assert_eq!(part3("A,B,C\n\nL6").unwrap(), "A");
     }
 }
@@ -1,20 +1,55 @@
-use crate::solutions::solution;
+use crate::solutions::Registration;
+use crate::solutions::grid::{Grid, Point};
+use crate::solutions::params::{Params, parse_value};
+use crate::solutions::solution::{self, Answer, Part, PuzzleParams, SolveError};
 use std::collections::{HashMap, HashSet};
 
 pub struct Day10Solver;
 
+pub const REGISTRATION: Registration = Registration {
+    day: 10,
+    title: "Feast on the Board",
+    parts: &[Part::One, Part::Two, Part::Three],
+    solver: &Day10Solver,
+};
+
 impl solution::Solver for Day10Solver {
-    fn solve(&self, input: solution::Input) -> solution::Solution {
-        solution::Solution {
-            part1: part1(&input.part1).to_string(),
-            part2: part2(&input.part2).to_string(),
-            part3: "".into(),
+    fn part1(&self, input: &str, params: &PuzzleParams) -> Result<Answer, SolveError> {
+        Ok(part1(
+            &solution::parsed(Board::try_from(input)?),
+            &Day10Params::from_params(params)?,
+        )
+        .into())
+    }
+
+    fn part2(&self, input: &str, params: &PuzzleParams) -> Result<Answer, SolveError> {
+        Ok(part2(
+            &solution::parsed(Board::try_from(input)?),
+            &Day10Params::from_params(params)?,
+        )
+        .into())
+    }
+
+    fn part3(&self, input: &str, _params: &PuzzleParams) -> Result<Answer, SolveError> {
+        Ok(part3(&solution::parsed(Board::try_from(input)?)).into())
+    }
+
+    fn check_params(&self, part: Part, params: &PuzzleParams) -> Result<(), SolveError> {
+        let params = Day10Params::from_params(params)?;
+
+        match part {
+            Part::Three if params.moves.is_some() => Err(SolveError::param(
+                "part 3 plays the game to its end, 'moves' only applies to parts 1 and 2",
+            )),
+            _ => Ok(()),
         }
     }
 }
 
-type Point = (usize, usize);
+type Herd = Vec<Point>;
+type Memo = HashMap<(Point, Herd), u64>;
 
+#[derive(Clone, Copy, PartialEq, Eq)]
 enum Element {
     Dragon,
     Sheep,
@@ -22,189 +57,188 @@ enum Element {
     Safe,
 }
 
-struct Grid {
-    grid: HashMap<Point, Element>,
-    limits: Point,
+/// The number of dragon moves of parts 1 and 2, which default to different values.
+#[derive(Default)]
+struct Day10Params {
+    moves: Option<i64>,
+}
+
+struct Board {
+    grid: Grid<Element>,
     dragon: Point,
 }
 
-fn part2(input: &str) -> u64 {
-    let grid = Grid::from(input);
+fn part3(board: &Board) -> u64 {
+    let herd: Herd = board
+        .grid
+        .iter()
+        .filter(|(_, element)| **element == Element::Sheep)
+        .map(|(pos, _)| pos)
+        .collect();
+
+    sheep_turn(board, board.dragon, sorted(herd), &mut HashMap::new())
+}
+
+/// Counts the move sequences in which the dragon eats every sheep, starting with the sheep.
+fn sheep_turn(board: &Board, dragon: Point, herd: Herd, memo: &mut Memo) -> u64 {
+    if let Some(count) = memo.get(&(dragon, herd.clone())) {
+        return *count;
+    }
+
+    let mut can_move = false;
+    let mut count = 0;
+
+    for (idx, (row, col)) in herd.iter().enumerate() {
+        let next = (row + 1, *col);
+
+        if !board.grid.contains(next) {
+            // The sheep escapes, so no sequence following this move counts.
+            can_move = true;
+            continue;
+        }
+        if next == dragon && !board.has_safe_space(next) {
+            continue;
+        }
 
-    let moves = moves(2);
-    let mut move_n = 0;
-    let mut to_visit = HashSet::from([grid.dragon]);
+        can_move = true;
+        let mut moved = herd.clone();
+        moved[idx] = next;
+        count += dragon_turn(board, dragon, sorted(moved), memo);
+    }
+
+    if !can_move {
+        count = dragon_turn(board, dragon, herd.clone(), memo);
+    }
+
+    memo.insert((dragon, herd), count);
+    count
+}
+
+fn dragon_turn(board: &Board, dragon: Point, herd: Herd, memo: &mut Memo) -> u64 {
+    board
+        .grid
+        .knight(dragon)
+        .map(|next| {
+            let survivors: Herd = herd
+                .iter()
+                .filter(|sheep| **sheep != next || board.has_safe_space(**sheep))
+                .copied()
+                .collect();
+
+            match survivors.is_empty() {
+                true => 1,
+                false => sheep_turn(board, next, survivors, memo),
+            }
+        })
+        .sum()
+}
+
+fn sorted(mut herd: Herd) -> Herd {
+    herd.sort();
+    herd
+}
+
+fn part2(board: &Board, params: &Day10Params) -> u64 {
+    let moves = params.moves.unwrap_or(20);
+    let mut to_visit = HashSet::from([board.dragon]);
     let mut sheep_pos: HashSet<Point> = HashSet::new();
 
-    while move_n != (moves + 1) {
+    for move_n in 0..=moves {
         let mut new_moves = HashSet::new();
-        for pos in to_visit.iter() {
-            if !(grid.has_safe_space(pos)) {
+        for &(row, col) in to_visit.iter() {
+            if !board.has_safe_space((row, col)) {
                 sheep_pos.extend(
-                    [
-                        (pos.0.wrapping_sub(move_n as usize), pos.1),
-                        ((pos.0 + 1).wrapping_sub(move_n as usize), pos.1),
-                    ]
-                    .iter()
-                    .filter(|pos| grid.has_sheep(pos)),
+                    [(row - move_n, col), (row + 1 - move_n, col)]
+                        .into_iter()
+                        .filter(|pos| board.has_sheep(*pos)),
                 );
             }
 
-            new_moves.extend(grid.next_moves(pos));
+            new_moves.extend(board.grid.knight((row, col)));
         }
 
         to_visit = new_moves;
-        move_n += 1;
     }
 
     sheep_pos.len() as u64
 }
 
-fn part1(input: &str) -> u64 {
-    let grid = Grid::from(input);
-
-    let moves = moves(1);
-    let mut move_n = 0;
-    let mut to_visit = vec![grid.dragon];
+fn part1(board: &Board, params: &Day10Params) -> u64 {
+    let moves = params.moves.unwrap_or(4);
+    let mut to_visit = vec![board.dragon];
     let mut sheep_pos: HashSet<Point> = HashSet::new();
 
-    while move_n != (moves + 1) {
+    for _ in 0..=moves {
         let mut new_moves = vec![];
         for pos in to_visit.iter() {
-            if grid.can_eat_sheep(pos) {
+            if board.can_eat_sheep(*pos) {
                 sheep_pos.insert(*pos);
             }
-            new_moves.extend(grid.next_moves(pos));
+            new_moves.extend(board.grid.knight(*pos));
         }
 
         to_visit = new_moves;
-        move_n += 1;
     }
 
     sheep_pos.len() as u64
 }
 
-fn moves(part: u8) -> u8 {
-    match (part, cfg!(test)) {
-        (1, true) => 3,
-        (1, false) => 4,
-        (2, true) => 3,
-        (2, false) => 20,
-        _ => unreachable!(),
+impl Params for Day10Params {
+    const KEYS: &'static [&'static str] = &["moves"];
+
+    fn set(&mut self, key: &str, value: &str) -> Result<(), SolveError> {
+        self.moves = Some(parse_value(key, value)?);
+        Ok(())
     }
 }
 
-impl Grid {
-    pub fn has_sheep(&self, pos: &Point) -> bool {
-        matches!(self.grid.get(pos), Some(Element::Sheep))
+impl Board {
+    pub fn has_sheep(&self, pos: Point) -> bool {
+        self.grid.get(pos) == Some(&Element::Sheep)
     }
 
-    pub fn has_safe_space(&self, pos: &Point) -> bool {
-        matches!(self.grid.get(pos), Some(Element::Safe))
+    pub fn has_safe_space(&self, pos: Point) -> bool {
+        self.grid.get(pos) == Some(&Element::Safe)
     }
 
-    pub fn can_eat_sheep(&self, pos: &Point) -> bool {
+    pub fn can_eat_sheep(&self, pos: Point) -> bool {
         self.has_sheep(pos) && !self.has_safe_space(pos)
     }
-
-    pub fn next_moves(&self, dragon_posiiton: &Point) -> Vec<Point> {
-        let x = dragon_posiiton.0 as i32;
-        let y = dragon_posiiton.1 as i32;
-        let (max_x, max_y) = self.limits;
-        let mov = [
-            (-1, -2),
-            (-1, 2),
-            (1, -2),
-            (1, 2),
-            (2, -1),
-            (2, 1),
-            (-2, 1),
-            (-2, -1),
-        ];
-
-        mov.iter()
-            .filter(|(dx, dy)| x + dx >= 0 && y + dy >= 0)
-            .map(|(dx, dy)| ((x + *dx) as usize, (y + *dy) as usize))
-            .filter(|(new_x, new_y)| *new_x <= max_x && *new_y <= max_y)
-            .collect()
-    }
 }
 
-impl From<&str> for Grid {
-    fn from(value: &str) -> Self {
-        let grid: HashMap<Point, Element> = value
-            .lines()
-            .enumerate()
-            .flat_map(|(row, line)| {
-                line.char_indices().map(move |(col, c)| match c {
-                    'D' => ((row, col), Element::Dragon),
-                    'S' => ((row, col), Element::Sheep),
-                    '#' => ((row, col), Element::Safe),
-                    '.' => ((row, col), Element::Empty),
-                    _ => unreachable!(),
-                })
-            })
-            .collect();
-
-        let (dragon_pos, _) = grid
-            .iter()
-            .find(|(_p, d)| matches!(d, Element::Dragon))
-            .unwrap();
-        let dragon = *dragon_pos;
-        let limits = *grid.keys().max().unwrap();
-
-        Self {
-            grid,
-            dragon,
-            limits,
-        }
+impl TryFrom<&str> for Board {
+    type Error = SolveError;
+
+    fn try_from(value: &str) -> Result<Self, Self::Error> {
+        let grid = Grid::parse(value, |c| match c {
+            'D' => Some(Element::Dragon),
+            'S' => Some(Element::Sheep),
+            '#' => Some(Element::Safe),
+            '.' => Some(Element::Empty),
+            _ => None,
+        })?;
+        let dragon = grid
+            .find(|element| *element == Element::Dragon)
+            .ok_or_else(|| SolveError::parse(value, "the board has no dragon"))?;
+
+        Ok(Self { grid, dragon })
     }
 }
 
 #[cfg(test)]
 mod tests {
-
-    use super::solution::Solver;
     use super::*;
+    use crate::solutions::solution::{Input, Solver};
 
     #[test]
-    fn test_example() {
-        let input_1 = r#"...SSS.......
-.S......S.SS.
-..S....S...S.
-..........SS.
-..SSSS...S...
-.....SS..S..S
-SS....D.S....
-S.S..S..S....
-....S.......S
-.SSS..SS.....
-.........S...
-.......S....S
-SS.....S..S.."#;
-
-        let input_2 = r####"...SSS##.....
-.S#.##..S#SS.
-..S.##.S#..S.
-.#..#S##..SS.
-..SSSS.#.S.#.
-.##..SS.#S.#S
-SS##.#D.S.#..
-S.S..S..S###.
-.##.S#.#....S
-.SSS.#SS..##.
-..#.##...S##.
-.#...#.S#...S
-SS...#.S.#S.."####;
-
-        let input = solution::Input {
-            part1: input_1.into(),
-            part2: input_2.into(),
-            part3: "".into(),
-        };
-
-        let solution = Day10Solver.solve(input);
-        assert_eq!(solution.part1, "27");
-        assert_eq!(solution.part2, "27");
+    fn test_check_params() {
+        let moves = PuzzleParams::default().with("moves", "3");
+        let mut input = Input::default();
+        input.set(Part::Three, "D".to_string());
+
+        assert!(Day10Solver.check_params(Part::One, &moves).is_ok());
+        assert!(Day10Solver.check_params(Part::Three, &moves).is_err());
+        assert!(Day10Solver.solve(&input, &moves).is_err());
+        assert!(Day10Solver.solve(&input, &PuzzleParams::default()).is_ok());
     }
 }
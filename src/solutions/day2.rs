@@ -1,166 +1,439 @@
-use regex;
+use num_bigint::{BigInt, BigUint, Sign};
+use regex::Regex;
+use std::ops::{Add, Div, Mul};
 use std::str::FromStr;
+use std::sync::LazyLock;
 
-use crate::solutions::solution;
+use crate::solutions::Registration;
+use crate::solutions::error::parse_number;
+use crate::solutions::grid::Grid;
+use crate::solutions::parallel;
+use crate::solutions::params::{Params, parse_value};
+use crate::solutions::solution::{self, Answer, Image, Part, PuzzleParams, SolveError};
 
-#[derive(PartialEq, Eq)]
-struct ComplexNumber {
-    real: i64,
-    imaginary: i64,
+#[derive(Clone, PartialEq, Eq, Debug)]
+struct ComplexNumber<T> {
+    real: T,
+    imaginary: T,
+}
+
+/// The integers a `ComplexNumber` is made of, whose operations report overflows.
+trait Integer: Clone + PartialEq + FromStr + Send + Sync {
+    fn from_i64(n: i64) -> Self;
+    fn checked_add(&self, other: &Self) -> Option<Self>;
+    fn checked_sub(&self, other: &Self) -> Option<Self>;
+    fn checked_mul(&self, other: &Self) -> Option<Self>;
+    /// Divides truncating toward zero, as the puzzle does.
+    fn checked_div(&self, other: &Self) -> Option<Self>;
+    fn to_i64(&self) -> Option<i64>;
+    /// Whether the absolute value is at least `limit`.
+    fn exceeds(&self, limit: i64) -> bool;
+}
+
+/// The integers used by the parts, `i64` being the fastest and `big` the only one that
+/// cannot overflow.
+#[derive(Clone, Copy, PartialEq, Eq, Debug, Default)]
+pub enum Backend {
+    #[default]
+    I64,
+    I128,
+    Big,
+}
+
+#[derive(Default)]
+struct Day2Params {
+    backend: Backend,
 }
 
 pub struct Day2Solver;
 
+pub const REGISTRATION: Registration = Registration {
+    day: 2,
+    title: "From Complex to Clarity",
+    parts: &[Part::One, Part::Two, Part::Three],
+    solver: &Day2Solver,
+};
+
 impl solution::Solver for Day2Solver {
-    fn solve(&self, input: solution::Input) -> solution::Solution {
-        solution::Solution {
-            part1: part1(&input.part1),
-            part2: part2(&input.part2),
-            part3: part3(&input.part3),
+    fn part1(&self, input: &str, params: &PuzzleParams) -> Result<Answer, SolveError> {
+        let backend = Day2Params::from_params(params)?.backend;
+
+        match backend {
+            Backend::I64 => part1::<i64>(input),
+            Backend::I128 => part1::<i128>(input),
+            Backend::Big => part1::<BigInt>(input),
         }
+        .map(Answer::from)
+    }
+
+    fn part2(&self, input: &str, params: &PuzzleParams) -> Result<Answer, SolveError> {
+        count(input, 10, Day2Params::from_params(params)?.backend).map(Answer::from)
     }
-}
 
-fn part3(input: &str) -> String {
-    let a = ComplexNumber::from_str(input).unwrap();
-    let step = 1;
+    fn part3(&self, input: &str, params: &PuzzleParams) -> Result<Answer, SolveError> {
+        count(input, 1, Day2Params::from_params(params)?.backend).map(Answer::from)
+    }
+
+    fn check_params(&self, _part: Part, params: &PuzzleParams) -> Result<(), SolveError> {
+        Day2Params::from_params(params).map(|_| ())
+    }
 
-    grid(&a, step)
-        .iter()
-        .filter(|c| engrave(c))
-        .count()
-        .to_string()
+    fn render(
+        &self,
+        part: Part,
+        input: &str,
+        params: &PuzzleParams,
+    ) -> Result<Option<Image>, SolveError> {
+        let Some(step) = step(part) else {
+            return Ok(None);
+        };
+
+        Ok(Some(Image::Levels {
+            levels: iteration_matrix(input, step, Day2Params::from_params(params)?.backend)?,
+            max: ITERATIONS,
+        }))
+    }
 }
 
-fn part2(input: &str) -> String {
-    let a = ComplexNumber::from_str(input).unwrap();
-    let step = 10;
+fn count(input: &str, step: usize, backend: Backend) -> Result<usize, SolveError> {
+    match backend {
+        Backend::I64 => solution::parsed(Engraving::<i64>::new(input, step)?).count(),
+        Backend::I128 => solution::parsed(Engraving::<i128>::new(input, step)?).count(),
+        Backend::Big => solution::parsed(Engraving::<BigInt>::new(input, step)?).count(),
+    }
+}
 
-    grid(&a, step)
-        .iter()
-        .filter(|c| engrave(c))
-        .count()
-        .to_string()
+/// The distance between the engraved points of a part, part 1 engraves nothing.
+fn step(part: Part) -> Option<usize> {
+    match part {
+        Part::One => None,
+        Part::Two => Some(10),
+        Part::Three => Some(1),
+    }
 }
 
-fn part1(input: &str) -> String {
-    let a = ComplexNumber::from_str(input).unwrap();
+fn part1<T: Integer>(input: &str) -> Result<(i64, i64), SolveError> {
+    let a = solution::parsed(ComplexNumber::<T>::from_str(input)?);
+    let tenten = ComplexNumber::new(10, 10);
 
-    let tenten = ComplexNumber {
-        real: 10,
-        imaginary: 10,
-    };
+    let mut result = ComplexNumber::new(0, 0);
+    for _ in 1..=3 {
+        let square = checked(&result * &result)?;
+        result = checked((&square / &tenten).and_then(|quotient| &quotient + &a))?;
+    }
+
+    match (result.real.to_i64(), result.imaginary.to_i64()) {
+        (Some(real), Some(imaginary)) => Ok((real, imaginary)),
+        _ => Err(SolveError::overflow("the result does not fit in an i64")),
+    }
+}
 
-    let result = (1..=3).fold(
-        ComplexNumber {
-            real: 0,
-            imaginary: 0,
-        },
-        |acc, _| acc.multiply(&acc).divide(&tenten).add(&a),
-    );
+/// The number of iterations a point goes through before a point is engraved.
+const ITERATIONS: u8 = 100;
 
-    format!("[{},{}]", result.real, result.imaginary)
+/// The square of points checked for engraving, `step` apart from `origin` up to 1000 away,
+/// with one row per imaginary part.
+struct Engraving<T> {
+    origin: ComplexNumber<T>,
+    step: usize,
+    size: usize,
 }
 
-fn grid(number: &ComplexNumber, step: usize) -> Vec<ComplexNumber> {
-    let mut v = Vec::new();
+/// The iteration counts of every point of the engraving of `input`, see `iterations`.
+pub fn iteration_matrix(
+    input: &str,
+    step: usize,
+    backend: Backend,
+) -> Result<Grid<u8>, SolveError> {
+    match backend {
+        Backend::I64 => Engraving::<i64>::new(input, step)?.iterations(),
+        Backend::I128 => Engraving::<i128>::new(input, step)?.iterations(),
+        Backend::Big => Engraving::<BigInt>::new(input, step)?.iterations(),
+    }
+}
 
-    for re in (number.real..=(number.real + 1000)).step_by(step) {
-        for im in (number.imaginary..=(number.imaginary + 1000)).step_by(step) {
-            v.push(ComplexNumber {
-                real: re,
-                imaginary: im,
-            });
-        }
+impl<T: Integer> Engraving<T> {
+    fn new(input: &str, step: usize) -> Result<Self, SolveError> {
+        Ok(Self {
+            origin: ComplexNumber::from_str(input)?,
+            step,
+            size: 1000 / step + 1,
+        })
+    }
+
+    /// Streams the points of a row, `None` for the ones out of the range of `T`.
+    fn row(&self, row: usize) -> impl Iterator<Item = Option<ComplexNumber<T>>> + '_ {
+        let offset = |n: usize| T::from_i64((n * self.step) as i64);
+
+        (0..self.size).map(move |col| {
+            Some(ComplexNumber {
+                real: self.origin.real.checked_add(&offset(col))?,
+                imaginary: self.origin.imaginary.checked_add(&offset(row))?,
+            })
+        })
+    }
+
+    /// The iterations of a point of a row, see `iterations`.
+    fn survived(&self, point: Option<ComplexNumber<T>>) -> Result<u8, SolveError> {
+        checked(point.and_then(|point| iterations(&point)))
+    }
+
+    /// Counts the engraved points, splitting the rows across threads.
+    fn count(&self) -> Result<usize, SolveError> {
+        let rows: Vec<usize> = (0..self.size).collect();
+
+        parallel::map(&rows, |row| {
+            self.row(*row).try_fold(0, |count, point| {
+                Ok(count + usize::from(self.survived(point)? == ITERATIONS))
+            })
+        })
+        .into_iter()
+        .sum()
+    }
+
+    fn iterations(&self) -> Result<Grid<u8>, SolveError> {
+        let rows: Vec<usize> = (0..self.size).collect();
+
+        let rows = parallel::map(&rows, |row| {
+            self.row(*row)
+                .map(|point| self.survived(point))
+                .collect::<Result<Vec<u8>, SolveError>>()
+        });
+        Ok(Grid::from_rows(rows.into_iter().collect::<Result<_, _>>()?))
     }
-    v
 }
 
-fn engrave(number: &ComplexNumber) -> bool {
-    let divisor = ComplexNumber {
-        real: 100_000,
-        imaginary: 100_000,
-    };
-    let mut start = ComplexNumber {
-        real: 0,
-        imaginary: 0,
-    };
-    for i in 1..=100 {
-        if i > 2 && start == *number {
-            return true;
+/// The iterations a point survives before diverging, `ITERATIONS` when it is engraved, or
+/// `None` when they overflow `T`.
+fn iterations<T: Integer>(point: &ComplexNumber<T>) -> Option<u8> {
+    // Built here rather than shared by the points, so that the primitive backends divide by a
+    // constant, which compiles to a multiplication.
+    let divisor = ComplexNumber::new(100_000, 100_000);
+    let mut start = ComplexNumber::new(0, 0);
+
+    for i in 1..=ITERATIONS {
+        if i > 2 && start == *point {
+            return Some(ITERATIONS);
         }
 
-        start = start.multiply(&start).divide(&divisor).add(number);
+        let square = (&start * &start)?;
+        start = (&(&square / &divisor)? + point)?;
 
-        if diverges(&start) {
-            return false;
+        if start.diverges() {
+            return Some(i - 1);
         }
     }
-    true
+    Some(ITERATIONS)
 }
 
-fn diverges(number: &ComplexNumber) -> bool {
-    number.imaginary.abs() >= 1_000_000 || number.real.abs() >= 1_000_000
+fn checked<T>(value: Option<T>) -> Result<T, SolveError> {
+    value.ok_or_else(|| SolveError::overflow("try --param backend=i128 or backend=big"))
 }
 
-impl ComplexNumber {
-    fn add(&self, other: &Self) -> Self {
+impl<T: Integer> ComplexNumber<T> {
+    fn new(real: i64, imaginary: i64) -> Self {
         Self {
-            real: self.real + other.real,
-            imaginary: self.imaginary + other.imaginary,
+            real: T::from_i64(real),
+            imaginary: T::from_i64(imaginary),
         }
     }
 
-    fn multiply(&self, other: &Self) -> Self {
-        // [X1,Y1] * [X2,Y2] = [X1 * X2 - Y1 * Y2, X1 * Y2 + Y1 * X2]
-        Self {
-            real: self.real * other.real - self.imaginary * other.imaginary,
-            imaginary: self.real * other.imaginary + self.imaginary * other.real,
-        }
+    fn diverges(&self) -> bool {
+        self.imaginary.exceeds(1_000_000) || self.real.exceeds(1_000_000)
     }
+}
 
-    fn divide(&self, other: &Self) -> Self {
-        // [X1,Y1] / [X2,Y2] = [X1 / X2, Y1 / Y2]
-        Self {
-            real: self.real / other.real,
-            imaginary: self.imaginary / other.imaginary,
-        }
+impl<T: Integer> Add for &ComplexNumber<T> {
+    type Output = Option<ComplexNumber<T>>;
+
+    fn add(self, other: Self) -> Self::Output {
+        Some(ComplexNumber {
+            real: self.real.checked_add(&other.real)?,
+            imaginary: self.imaginary.checked_add(&other.imaginary)?,
+        })
     }
 }
 
-#[derive(Debug)]
-struct ParseError;
+impl<T: Integer> Mul for &ComplexNumber<T> {
+    type Output = Option<ComplexNumber<T>>;
 
-impl FromStr for ComplexNumber {
-    type Err = ParseError;
+    // [X1,Y1] * [X2,Y2] = [X1 * X2 - Y1 * Y2, X1 * Y2 + Y1 * X2]
+    fn mul(self, other: Self) -> Self::Output {
+        Some(ComplexNumber {
+            real: self
+                .real
+                .checked_mul(&other.real)?
+                .checked_sub(&self.imaginary.checked_mul(&other.imaginary)?)?,
+            imaginary: self
+                .real
+                .checked_mul(&other.imaginary)?
+                .checked_add(&self.imaginary.checked_mul(&other.real)?)?,
+        })
+    }
+}
 
-    fn from_str(s: &str) -> Result<Self, Self::Err> {
-        let r = regex::Regex::new(r"\[(?P<real>-?\d+),(?P<imaginary>-?\d+)\]").unwrap();
+impl<T: Integer> Div for &ComplexNumber<T> {
+    type Output = Option<ComplexNumber<T>>;
+
+    // [X1,Y1] / [X2,Y2] = [X1 / X2, Y1 / Y2]
+    fn div(self, other: Self) -> Self::Output {
+        Some(ComplexNumber {
+            real: self.real.checked_div(&other.real)?,
+            imaginary: self.imaginary.checked_div(&other.imaginary)?,
+        })
+    }
+}
+
+macro_rules! primitive_integer {
+    ($($t:ty),*) => {
+        $(
+            impl Integer for $t {
+                fn from_i64(n: i64) -> Self {
+                    <$t>::from(n)
+                }
+
+                fn checked_add(&self, other: &Self) -> Option<Self> {
+                    <$t>::checked_add(*self, *other)
+                }
+
+                fn checked_sub(&self, other: &Self) -> Option<Self> {
+                    <$t>::checked_sub(*self, *other)
+                }
+
+                fn checked_mul(&self, other: &Self) -> Option<Self> {
+                    <$t>::checked_mul(*self, *other)
+                }
+
+                fn checked_div(&self, other: &Self) -> Option<Self> {
+                    <$t>::checked_div(*self, *other)
+                }
+
+                fn to_i64(&self) -> Option<i64> {
+                    i64::try_from(*self).ok()
+                }
 
-        let caps = r.captures(s).unwrap();
+                fn exceeds(&self, limit: i64) -> bool {
+                    self.checked_abs().is_none_or(|abs| abs >= <$t>::from(limit))
+                }
+            }
+        )*
+    };
+}
+
+primitive_integer!(i64, i128);
+
+impl Integer for BigInt {
+    fn from_i64(n: i64) -> Self {
+        BigInt::from(n)
+    }
+
+    fn checked_add(&self, other: &Self) -> Option<Self> {
+        Some(self + other)
+    }
+
+    fn checked_sub(&self, other: &Self) -> Option<Self> {
+        Some(self - other)
+    }
+
+    fn checked_mul(&self, other: &Self) -> Option<Self> {
+        Some(self * other)
+    }
+
+    fn checked_div(&self, other: &Self) -> Option<Self> {
+        (other.sign() != Sign::NoSign).then(|| self / other)
+    }
+
+    fn to_i64(&self) -> Option<i64> {
+        i64::try_from(self).ok()
+    }
+
+    fn exceeds(&self, limit: i64) -> bool {
+        *self.magnitude() >= BigUint::from(limit.unsigned_abs())
+    }
+}
+
+static COMPLEX_NUMBER: LazyLock<Regex> =
+    LazyLock::new(|| Regex::new(r"\[(?P<real>-?\d+),(?P<imaginary>-?\d+)\]").unwrap());
+
+impl<T: Integer> FromStr for ComplexNumber<T> {
+    type Err = SolveError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let caps = COMPLEX_NUMBER
+            .captures(s)
+            .ok_or_else(|| SolveError::parse(s, "expected a complex number like '[25,9]'"))?;
         Ok(Self {
-            real: caps["real"].parse().unwrap(),
-            imaginary: caps["imaginary"].parse().unwrap(),
+            real: parse_number(caps.name("real").unwrap().as_str())?,
+            imaginary: parse_number(caps.name("imaginary").unwrap().as_str())?,
         })
     }
 }
 
+impl FromStr for Backend {
+    type Err = ();
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "i64" => Ok(Backend::I64),
+            "i128" => Ok(Backend::I128),
+            "big" => Ok(Backend::Big),
+            _ => Err(()),
+        }
+    }
+}
+
+impl Params for Day2Params {
+    const KEYS: &'static [&'static str] = &["backend"];
+
+    fn set(&mut self, key: &str, value: &str) -> Result<(), SolveError> {
+        self.backend = parse_value(key, value)?;
+        Ok(())
+    }
+}
+
 #[cfg(test)]
 mod tests {
-    use super::solution::Solver;
     use super::*;
 
     #[test]
-    fn test_solve() {
-        let input = solution::Input {
-            part1: "A=[25,9]".into(),
-            part2: "A=[35300,-64910]".into(),
-            part3: "A=[35300,-64910]".into(),
-        };
+    fn test_iteration_matrix() {
+        let matrix = iteration_matrix("A=[35300,-64910]", 10, Backend::I64).unwrap();
+        let engraved = matrix.iter().filter(|(_, n)| **n == ITERATIONS).count();
+
+        assert_eq!((matrix.rows(), matrix.cols()), (101, 101));
+        assert_eq!(engraved, 4076);
+        assert!(matrix[(0, 0)] < ITERATIONS);
+        assert_eq!(
+            iteration_matrix("A=[35300,-64910]", 10, Backend::Big).unwrap(),
+            matrix
+        );
+    }
+
+    #[test]
+    fn test_backends() {
+        let big = ComplexNumber::<i64>::new(i64::MAX, 1);
+        let one = ComplexNumber::new(1, 1);
+
+        assert_eq!(&big + &one, None);
+        assert!(matches!(checked(&big * &big), Err(SolveError::Overflow(_))));
+        assert_eq!(
+            (&ComplexNumber::<i64>::new(-7, 7) / &ComplexNumber::new(2, 2)).unwrap(),
+            ComplexNumber::new(-3, 3)
+        );
+
+        let wide = ComplexNumber::<i128>::new(i64::MAX, 1);
+        assert!((&wide * &wide).is_some());
+        let huge = ComplexNumber::<BigInt>::new(i64::MAX, 1);
+        let square = (&huge * &huge).unwrap();
+        assert_eq!(
+            (&(&square * &square).unwrap() * &square)
+                .unwrap()
+                .real
+                .to_i64(),
+            None
+        );
 
-        let solution = Day2Solver.solve(input);
-        assert_eq!(solution.part1, "[357,862]");
-        assert_eq!(solution.part2, "4076");
-        assert_eq!(solution.part3, "406954");
+        assert_eq!(part1::<BigInt>("A=[25,9]").unwrap(), (357, 862));
+        assert_eq!(part1::<i128>("A=[25,9]").unwrap(), (357, 862));
     }
 }
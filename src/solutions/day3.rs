@@ -1,15 +1,28 @@
-use crate::solutions::solution;
+use crate::solutions::Registration;
+use crate::solutions::error::parse_number;
+use crate::solutions::solution::{self, Answer, Part, PuzzleParams, SolveError};
 use std::collections::{HashMap, HashSet};
 
 pub struct Day3Solver;
 
+pub const REGISTRATION: Registration = Registration {
+    day: 3,
+    title: "The Deepest Fit",
+    parts: &[Part::One, Part::Two, Part::Three],
+    solver: &Day3Solver,
+};
+
 impl solution::Solver for Day3Solver {
-    fn solve(&self, input: solution::Input) -> solution::Solution {
-        solution::Solution {
-            part1: part1(&parse(&input.part1)).to_string(),
-            part2: part2(&parse(&input.part2)).to_string(),
-            part3: part3(&parse(&input.part3)).to_string(),
-        }
+    fn part1(&self, input: &str, _params: &PuzzleParams) -> Result<Answer, SolveError> {
+        Ok(part1(&solution::parsed(parse(input)?)).into())
+    }
+
+    fn part2(&self, input: &str, _params: &PuzzleParams) -> Result<Answer, SolveError> {
+        Ok(part2(&solution::parsed(parse(input)?)).into())
+    }
+
+    fn part3(&self, input: &str, _params: &PuzzleParams) -> Result<Answer, SolveError> {
+        Ok(part3(&solution::parsed(parse(input)?))?.into())
     }
 }
 
@@ -28,37 +41,20 @@ fn part2(nums: &[u32]) -> u32 {
     n.iter().take(20).sum()
 }
 
-fn part3(nums: &[u32]) -> u32 {
+fn part3(nums: &[u32]) -> Result<u32, SolveError> {
     let mut freqs = HashMap::new();
 
     for n in nums {
         *freqs.entry(n).or_default() += 1;
     }
 
-    *freqs.values().max().unwrap()
+    freqs
+        .values()
+        .max()
+        .copied()
+        .ok_or_else(|| SolveError::no_answer("there are no crates"))
 }
 
-fn parse(input: &str) -> Vec<u32> {
-    input.split(",").map(|n| n.parse().unwrap()).collect()
-}
-
-#[cfg(test)]
-mod tests {
-    use super::solution::Solver;
-    use super::*;
-
-    #[test]
-    fn test_input() {
-        let input = solution::Input {
-            part1: "10,5,1,10,3,8,5,2,2".into(),
-            part2: "4,51,13,64,57,51,82,57,16,88,89,48,32,49,49,2,84,65,49,43,9,13,2,3,75,72,63,48,61,14,40,77".into(),
-            part3: "4,51,13,64,57,51,82,57,16,88,89,48,32,49,49,2,84,65,49,43,9,13,2,3,75,72,63,48,61,14,40,77".into(),
-        };
-
-        let solution = Day3Solver.solve(input);
-
-        assert_eq!(solution.part1, "29");
-        assert_eq!(solution.part2, "781");
-        assert_eq!(solution.part3, "3");
-    }
+fn parse(input: &str) -> Result<Vec<u32>, SolveError> {
+    input.split(",").map(parse_number).collect()
 }
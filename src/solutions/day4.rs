@@ -1,88 +1,110 @@
-use crate::solutions::solution;
+use crate::solutions::Registration;
+use crate::solutions::error::parse_number;
+use crate::solutions::solution::{self, Answer, Part, PuzzleParams, SolveError};
+use std::str::FromStr;
 
 pub struct Day4Solver;
 
+pub const REGISTRATION: Registration = Registration {
+    day: 4,
+    title: "Teeth of the Wind",
+    parts: &[Part::One, Part::Two, Part::Three],
+    solver: &Day4Solver,
+};
+
 impl solution::Solver for Day4Solver {
-    fn solve(&self, input: solution::Input) -> solution::Solution {
-        solution::Solution {
-            part1: part1(&input.part1).to_string(),
-            part2: part2(&input.part2).to_string(),
-            part3: part3(&input.part3).to_string(),
-        }
+    fn part1(&self, input: &str, _params: &PuzzleParams) -> Result<Answer, SolveError> {
+        Ok(part1(input)?.into())
+    }
+
+    fn part2(&self, input: &str, _params: &PuzzleParams) -> Result<Answer, SolveError> {
+        Ok(part2(input)?.into())
+    }
+
+    fn part3(&self, input: &str, _params: &PuzzleParams) -> Result<Answer, SolveError> {
+        Ok(part3(input)?.into())
     }
 }
 
-fn part1(input: &str) -> u32 {
-    let nums = parse(input);
-    2025 * nums[0] / nums[nums.len() - 1]
+fn part1(input: &str) -> Result<u32, SolveError> {
+    let nums = solution::parsed(parse(input)?);
+    Ok(2025 * nums[0] / nums[nums.len() - 1])
 }
 
-fn part2(input: &str) -> f64 {
-    let nums = parse(input);
+fn part2(input: &str) -> Result<i128, SolveError> {
+    let nums = solution::parsed(parse(input)?);
+    let (first, last) = (i128::from(nums[0]), i128::from(nums[nums.len() - 1]));
 
-    ((10_000_000_000_000f64 / (nums[0] as f64)) * nums[nums.len() - 1] as f64).ceil()
+    // Rounded up, both counts of teeth being positive.
+    Ok((10_000_000_000_000 * last + first - 1) / first)
 }
 
-fn part3(input: &str) -> u64 {
-    let gears = parse_3(input);
+fn part3(input: &str) -> Result<u64, SolveError> {
+    let gears = solution::parsed(parse_3(input)?);
     let final_ratio: u64 = gears[1..gears.len() - 1].iter().product();
 
-    (100 * gears[0] * final_ratio) / gears[gears.len() - 1]
+    Ok((100 * gears[0] * final_ratio) / gears[gears.len() - 1])
 }
 
-fn parse_3(input: &str) -> Vec<u64> {
-    input.lines().map(to_gear_ratio).collect()
+fn parse_3(input: &str) -> Result<Vec<u64>, SolveError> {
+    let gears: Vec<u64> = input.lines().map(to_gear_ratio).collect::<Result<_, _>>()?;
+
+    match gears[..] {
+        [_, _, ..] => Ok(gears),
+        _ => Err(SolveError::parse(input, "expected at least two gears")),
+    }
 }
 
-fn to_gear_ratio(input: &str) -> u64 {
+fn to_gear_ratio(input: &str) -> Result<u64, SolveError> {
     let nums = input.split("|").collect::<Vec<&str>>();
 
     match nums[..] {
-        [x] => x.parse().unwrap(),
+        [x] => teeth(x),
         [l, r] => {
-            let right: u64 = r.parse().unwrap();
-            let left: u64 = l.parse().unwrap();
-            right / left
+            let right: u64 = teeth(r)?;
+            let left: u64 = teeth(l)?;
+            Ok(right / left)
         }
-        _ => panic!("Unknown result {input}"),
+        _ => Err(SolveError::parse(
+            input,
+            "expected a gear like '5' or '7|21'",
+        )),
+    }
+}
+
+fn parse(input: &str) -> Result<Vec<u32>, SolveError> {
+    let gears: Vec<u32> = input.lines().map(teeth).collect::<Result<_, _>>()?;
+
+    match gears[..] {
+        [_, _, ..] => Ok(gears),
+        _ => Err(SolveError::parse(input, "expected at least two gears")),
     }
 }
 
-fn parse(input: &str) -> Vec<u32> {
-    input.lines().map(|l| l.parse().unwrap()).collect()
+/// The number of teeth of a gear, which the ratios divide by.
+fn teeth<T: FromStr + Default + PartialEq>(fragment: &str) -> Result<T, SolveError> {
+    let teeth = parse_number(fragment)?;
+    if teeth == T::default() {
+        return Err(SolveError::parse(
+            fragment,
+            "a gear needs at least one tooth",
+        ));
+    }
+    Ok(teeth)
 }
 
 #[cfg(test)]
 mod tests {
-    use super::solution::Solver;
     use super::*;
 
     #[test]
-    fn test_input() {
-        let p1 = r#"102
-75
-50
-35
-13"#;
-
-        let p3 = r#"5
-7|21
-18|36
-27|27
-10|50
-10|50
-11"#;
-
-        let input = solution::Input {
-            part1: p1.into(),
-            part2: p1.into(),
-            part3: p3.into(),
-        };
-
-        let solution = Day4Solver.solve(input);
-
-        assert_eq!(solution.part1, "15888");
-        assert_eq!(solution.part2, "1274509803922");
-        assert_eq!(solution.part3, "6818");
+    fn test_toothless_gear() {
+        let input = "128\n0";
+        let err = parse(input).unwrap_err().locate(input);
+
+        assert!(err.to_string().contains("at least one tooth"));
+        assert!(parse_3("5\n0|10\n5").is_err());
+        assert_eq!(part2("128\n64").unwrap(), 5_000_000_000_000);
+        assert_eq!(part2("3\n1").unwrap(), 3_333_333_333_334);
     }
 }
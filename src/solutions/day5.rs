@@ -1,147 +1,230 @@
-use crate::solutions::solution;
-use std::{cmp::Ordering, fmt::Error, str::FromStr};
+use crate::solutions::Registration;
+use crate::solutions::error::parse_number;
+use crate::solutions::solution::{self, Answer, Part, PuzzleParams, SolveError};
+use std::fmt;
+use std::{cmp::Ordering, str::FromStr};
 
 pub struct Day5Solver;
 
-#[derive(PartialEq, Eq)]
-struct Segment(Option<u64>, u64, Option<u64>);
+pub const REGISTRATION: Registration = Registration {
+    day: 5,
+    title: "Fishbone Order",
+    parts: &[Part::One, Part::Two, Part::Three],
+    solver: &Day5Solver,
+};
+
+/// A spine of numbers, each of which can grow a smaller branch on its left and a larger one
+/// on its right.
+#[derive(PartialEq, Eq, Default, Debug)]
+pub struct Fishbone {
+    segments: Vec<Segment>,
+}
+
+#[derive(PartialEq, Eq, Debug)]
+struct Segment {
+    left: Option<u64>,
+    spine: u64,
+    right: Option<u64>,
+}
 
 struct Sword {
     identifier: u64,
-    fishbone: Vec<Segment>,
+    quality: u64,
+    levels: Vec<u64>,
 }
 
 impl solution::Solver for Day5Solver {
-    fn solve(&self, input: solution::Input) -> solution::Solution {
-        solution::Solution {
-            part1: part1(&input.part1).to_string(),
-            part2: part2(&input.part2).to_string(),
-            part3: part3(&input.part3).to_string(),
-        }
+    fn part1(&self, input: &str, _params: &PuzzleParams) -> Result<Answer, SolveError> {
+        Ok(part1(input)?.into())
+    }
+
+    fn part2(&self, input: &str, _params: &PuzzleParams) -> Result<Answer, SolveError> {
+        Ok(part2(input)?.into())
+    }
+
+    fn part3(&self, input: &str, _params: &PuzzleParams) -> Result<Answer, SolveError> {
+        Ok(part3(input)?.into())
     }
 }
 
-fn part3(input: &str) -> u64 {
-    let mut swords: Vec<Sword> = input.lines().map(|s| Sword::from_str(s).unwrap()).collect();
+fn part3(input: &str) -> Result<u64, SolveError> {
+    let mut swords = solution::parsed(parse(input)?);
     swords.sort();
     swords.reverse();
 
-    swords
+    Ok(swords
         .iter()
         .enumerate()
         .map(|(idx, sword)| sword.identifier * (idx + 1) as u64)
-        .sum()
+        .sum())
 }
 
-fn part2(input: &str) -> u64 {
-    let swords: Vec<Sword> = input.lines().map(|s| Sword::from_str(s).unwrap()).collect();
+fn part2(input: &str) -> Result<u64, SolveError> {
+    let swords = solution::parsed(parse(input)?);
 
-    let mut qualities: Vec<u64> = swords.iter().map(|s| s.quality()).collect();
+    let mut qualities: Vec<u64> = swords.iter().map(|s| s.quality).collect();
     qualities.sort();
 
-    qualities[qualities.len() - 1] - qualities[0]
+    match (qualities.first(), qualities.last()) {
+        (Some(min), Some(max)) => Ok(max - min),
+        _ => Err(SolveError::no_answer("there are no swords")),
+    }
+}
+
+fn part1(input: &str) -> Result<u64, SolveError> {
+    let line = input
+        .lines()
+        .next()
+        .ok_or_else(|| SolveError::parse(input, "expected a sword"))?;
+
+    Ok(solution::parsed(Sword::from_str(line)?).quality)
 }
 
-fn part1(input: &str) -> u64 {
-    Sword::from_str(input.lines().next().unwrap())
-        .unwrap()
-        .quality()
+fn parse(input: &str) -> Result<Vec<Sword>, SolveError> {
+    input.lines().map(Sword::from_str).collect()
 }
 
-fn fishbone(numbers: &[u64]) -> Vec<Segment> {
-    let mut fishbone = Vec::new();
+impl Fishbone {
+    /// Places `number` on the first segment with a free branch on its side, or grows the
+    /// spine when there is none.
+    pub fn push(&mut self, number: u64) {
+        for segment in &mut self.segments {
+            if number < segment.spine && segment.left.is_none() {
+                segment.left = Some(number);
+                return;
+            }
+            if number > segment.spine && segment.right.is_none() {
+                segment.right = Some(number);
+                return;
+            }
+        }
+
+        self.segments.push(Segment {
+            left: None,
+            spine: number,
+            right: None,
+        });
+    }
 
-    for n in numbers {
-        add_to_fishbone(&mut fishbone, *n);
+    pub fn spine(&self) -> impl Iterator<Item = u64> + '_ {
+        self.segments.iter().map(|segment| segment.spine)
     }
 
-    fishbone
+    /// The numbers read across each segment, from the left branch to the right one.
+    pub fn levels(&self) -> impl Iterator<Item = u64> + '_ {
+        self.segments.iter().map(|segment| {
+            [segment.left, Some(segment.spine), segment.right]
+                .into_iter()
+                .flatten()
+                .fold(0, concat)
+        })
+    }
+
+    /// The number read down the spine.
+    pub fn quality(&self) -> u64 {
+        self.spine().fold(0, concat)
+    }
 }
 
-fn add_to_fishbone(fishbone: &mut Vec<Segment>, number: u64) {
-    let mut im = fishbone.iter_mut();
+/// Appends the digits of `b` to `a`.
+fn concat(a: u64, b: u64) -> u64 {
+    let mut shift = 10;
+    while shift <= b {
+        shift *= 10;
+    }
+    a * shift + b
+}
 
-    loop {
-        let s = im.next();
+impl FromIterator<u64> for Fishbone {
+    fn from_iter<I: IntoIterator<Item = u64>>(numbers: I) -> Self {
+        let mut fishbone = Fishbone::default();
+        for number in numbers {
+            fishbone.push(number);
+        }
+        fishbone
+    }
+}
 
-        match s {
-            None => {
-                fishbone.push(Segment(None, number, None));
-                break;
+/// Draws the spine down the middle, joined to its branches by dashes:
+///
+/// ```text
+/// 3-5-7
+///   |
+/// 5-10
+/// ```
+impl fmt::Display for Fishbone {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let indent = self
+            .segments
+            .iter()
+            .filter_map(|segment| segment.left)
+            .map(|n| n.to_string().len() + 1)
+            .max()
+            .unwrap_or(0);
+
+        for (idx, segment) in self.segments.iter().enumerate() {
+            if idx > 0 {
+                writeln!(f, "{:indent$}|", "")?;
             }
-            Some(Segment(None, m, _)) if number < *m => {
-                s.unwrap().0 = Some(number);
-                break;
+
+            match segment.left {
+                Some(n) => write!(f, "{:>width$}-", n, width = indent - 1)?,
+                None => write!(f, "{:indent$}", "")?,
             }
-            Some(Segment(_, m, None)) if number > *m => {
-                s.unwrap().2 = Some(number);
-                break;
+            write!(f, "{}", segment.spine)?;
+            if let Some(n) = segment.right {
+                write!(f, "-{n}")?;
             }
-            _ => (),
+            writeln!(f)?;
         }
-    }
-}
-
-// This should be moved to a Segment -> u64 trait
-fn segment_to_number(segment: &Segment) -> u64 {
-    match &segment {
-        Segment(None, n, None) => *n,
-        Segment(Some(l), n, None) => format!("{}{}", l, n).parse().unwrap(),
-        Segment(None, n, Some(r)) => format!("{}{}", n, r).parse().unwrap(),
-        Segment(Some(l), n, Some(r)) => format!("{}{}{}", l, n, r).parse().unwrap(),
+        Ok(())
     }
 }
 
 impl Sword {
-    fn quality(&self) -> u64 {
-        self.fishbone
-            .iter()
-            .map(|segment| segment.1.to_string())
-            .collect::<Vec<String>>()
-            .join("")
-            .parse::<u64>()
-            .unwrap()
+    fn new(identifier: u64, fishbone: &Fishbone) -> Self {
+        Self {
+            identifier,
+            quality: fishbone.quality(),
+            levels: fishbone.levels().collect(),
+        }
     }
 }
 
 impl FromStr for Sword {
-    type Err = Error;
+    type Err = SolveError;
     fn from_str(s: &str) -> Result<Self, Self::Err> {
-        let mut parser = s.split(":");
-        let identifier = parser.next().unwrap().parse().unwrap();
+        let (identifier, numbers) = s
+            .split_once(":")
+            .ok_or_else(|| SolveError::parse(s, "expected a sword like '58:5,3,7'"))?;
+        let identifier = parse_number(identifier)?;
 
-        let numbers: Vec<u64> = parser
-            .next()
-            .unwrap()
+        let fishbone = numbers
             .split(",")
-            .map(|n| n.parse().unwrap())
-            .collect();
+            .map(parse_number)
+            .collect::<Result<_, _>>()?;
 
-        Ok(Self {
-            identifier,
-            fishbone: fishbone(&numbers),
-        })
+        Ok(Sword::new(identifier, &fishbone))
     }
 }
 
+/// Orders by quality, then by the levels of the segments the swords both have, then by
+/// identifier.
 impl Ord for Sword {
     fn cmp(&self, other: &Self) -> Ordering {
-        match self.quality().cmp(&other.quality()) {
-            Ordering::Equal => {
-                for (f1, f2) in self.fishbone.iter().zip(&other.fishbone) {
-                    let first = segment_to_number(f1);
-                    let second: u64 = segment_to_number(f2);
-
-                    match first.cmp(&second) {
-                        Ordering::Equal => {}
-                        other => return other,
-                    }
-                }
-
-                self.identifier.cmp(&other.identifier)
-            }
-            other => other,
-        }
+        let levels = || {
+            self.levels
+                .iter()
+                .zip(&other.levels)
+                .map(|(a, b)| a.cmp(b))
+                .find(|ordering| ordering.is_ne())
+                .unwrap_or(Ordering::Equal)
+        };
+
+        self.quality
+            .cmp(&other.quality)
+            .then_with(levels)
+            .then_with(|| self.identifier.cmp(&other.identifier))
     }
 }
 
@@ -153,7 +236,7 @@ impl PartialOrd for Sword {
 
 impl PartialEq for Sword {
     fn eq(&self, other: &Self) -> bool {
-        self.identifier == other.identifier && (self.fishbone == other.fishbone)
+        self.cmp(other).is_eq()
     }
 }
 
@@ -161,42 +244,34 @@ impl Eq for Sword {}
 
 #[cfg(test)]
 mod tests {
-    use super::solution::Solver;
     use super::*;
 
     #[test]
-    fn test_input() {
-        let p2 = r#"1:2,4,1,1,8,2,7,9,8,6
-2:7,9,9,3,8,3,8,8,6,8
-3:4,7,6,9,1,8,3,7,2,2
-4:6,4,2,1,7,4,5,5,5,8
-5:2,9,3,8,3,9,5,2,1,4
-6:2,4,9,6,7,4,1,7,6,8
-7:2,3,7,6,2,2,4,1,4,2
-8:5,1,5,6,8,3,1,8,3,9
-9:5,7,7,3,7,2,3,8,6,7
-10:4,1,9,3,8,5,4,3,5,5"#;
-
-        let p3 = r#"1:7,1,9,1,6,9,8,3,7,2
-2:6,1,9,2,9,8,8,4,3,1
-3:7,1,9,1,6,9,8,3,8,3
-4:6,1,9,2,8,8,8,4,3,1
-5:7,1,9,1,6,9,8,3,7,3
-6:6,1,9,2,8,8,8,4,3,5
-7:3,7,2,2,7,4,4,6,3,1
-8:3,7,2,2,7,4,4,6,3,7
-9:3,7,2,2,7,4,1,6,3,7"#;
-
-        let input = solution::Input {
-            part1: "58:5,3,7,8,9,10,4,5,7,8,8".into(),
-            part2: p2.into(),
-            part3: p3.into(),
-        };
+    fn test_fishbone() {
+        let fishbone: Fishbone = [5, 3, 7, 8, 9, 10, 4, 5, 7, 8, 8].into_iter().collect();
+
+        assert_eq!(fishbone.spine().collect::<Vec<_>>(), vec![5, 8, 10, 7, 8]);
+        assert_eq!(
+            fishbone.levels().collect::<Vec<_>>(),
+            vec![357, 489, 510, 78, 8]
+        );
+        assert_eq!(fishbone.quality(), 581078);
+        assert_eq!(
+            fishbone.to_string(),
+            "3-5-7\n  |\n4-8-9\n  |\n5-10\n  |\n  7-8\n  |\n  8\n"
+        );
+    }
+
+    #[test]
+    fn test_sword_order() {
+        let short: Sword = "1:5,3,7".parse().unwrap();
+        let long: Sword = "1:5,3,7,5".parse().unwrap();
+        let stronger: Sword = "2:5,4,7".parse().unwrap();
 
-        let solution = Day5Solver.solve(input);
+        assert_eq!(short.cmp(&long), Ordering::Less);
+        assert_eq!(stronger.cmp(&short), Ordering::Greater);
 
-        assert_eq!(solution.part1, "581078");
-        assert_eq!(solution.part2, "77053");
-        assert_eq!(solution.part3, "260");
+        let same_key: Sword = "1:5,3,7,5".parse().unwrap();
+        assert!(long == same_key && long.cmp(&same_key).is_eq());
     }
 }
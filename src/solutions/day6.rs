@@ -1,21 +1,44 @@
 use std::collections::HashMap;
 
-use crate::solutions::solution;
+use crate::solutions::Registration;
+use crate::solutions::params::{Params, parse_value};
+use crate::solutions::solution::{self, Answer, Part, PuzzleParams, SolveError};
 
 pub struct Day6Solver;
 
+pub const REGISTRATION: Registration = Registration {
+    day: 6,
+    title: "Mentorship Matrix",
+    parts: &[Part::One, Part::Two, Part::Three],
+    solver: &Day6Solver,
+};
+
 type Frequencies = HashMap<char, u64>;
 
 impl solution::Solver for Day6Solver {
-    fn solve(&self, input: solution::Input) -> solution::Solution {
-        solution::Solution {
-            part1: part1(&input.part1).to_string(),
-            part2: part2(&input.part2).to_string(),
-            part3: part3(&input.part3).to_string(),
-        }
+    fn part1(&self, input: &str, _params: &PuzzleParams) -> Result<Answer, SolveError> {
+        Ok(part1(input).into())
+    }
+
+    fn part2(&self, input: &str, _params: &PuzzleParams) -> Result<Answer, SolveError> {
+        Ok(part2(input)?.into())
+    }
+
+    fn part3(&self, input: &str, params: &PuzzleParams) -> Result<Answer, SolveError> {
+        Ok(part3(input, &Day6Params::from_params(params)?).into())
+    }
+
+    fn check_params(&self, _part: Part, params: &PuzzleParams) -> Result<(), SolveError> {
+        Day6Params::from_params(params).map(|_| ())
     }
 }
 
+/// How far a mentor reaches and how many times the pattern repeats in part 3.
+struct Day6Params {
+    distance: i64,
+    repeats: u64,
+}
+
 fn part1(input: &str) -> u64 {
     let mut combinations = 0;
     let mut mentors = 0;
@@ -30,27 +53,28 @@ fn part1(input: &str) -> u64 {
     combinations
 }
 
-fn part2(input: &str) -> u64 {
+fn part2(input: &str) -> Result<u64, SolveError> {
     let mut combinations = 0;
     let mut mentors: Frequencies = HashMap::new();
 
-    for c in input.chars() {
+    for (idx, c) in input.char_indices() {
         if c.is_ascii_uppercase() {
             *mentors.entry(c).or_default() += 1;
         } else if c.is_ascii_lowercase() {
             combinations += *mentors.entry(c.to_ascii_uppercase()).or_default();
         } else {
-            panic!("Unexpected character: '{c}'");
+            let fragment = &input[idx..idx + c.len_utf8()];
+            return Err(SolveError::parse(fragment, "unexpected character"));
         }
     }
 
-    combinations
+    Ok(combinations)
 }
 
-fn part3(input: &str) -> u64 {
-    let characters: Vec<char> = input.chars().collect();
+fn part3(input: &str, params: &Day6Params) -> u64 {
+    let characters: Vec<char> = solution::parsed(input.chars().collect());
     let length = characters.len();
-    let window_size = 1000;
+    let window_size = params.distance;
     let mut combinations = 0;
 
     for (i, c) in characters.iter().enumerate() {
@@ -60,35 +84,41 @@ fn part3(input: &str) -> u64 {
         let mentor = c.to_ascii_uppercase();
 
         for n in -window_size..=window_size {
-            // If it wrapped then add 999, else 1000
+            // A wrapped pair exists once less than the number of repeats
             let idx_unrwapped = (i as i64) + n;
             let idx = idx_unrwapped.rem_euclid(length as i64);
 
             if characters[idx as usize] == mentor {
-                let mult = if idx == idx_unrwapped { 1000 } else { 999 };
+                let mult = if idx == idx_unrwapped {
+                    params.repeats
+                } else {
+                    params.repeats.saturating_sub(1)
+                };
                 combinations += mult;
             }
         }
     }
 
-    combinations as u64
+    combinations
 }
 
-#[cfg(test)]
-mod tests {
-    use super::solution::Solver;
-    use super::*;
-
-    #[test]
-    fn test_solve() {
-        let input = solution::Input {
-            part1: "ABabACacBCbca".into(),
-            part2: "ABabACacBCbca".into(),
-            part3: "AABCBABCABCabcabcABCCBAACBCa".into(),
-        };
-
-        let solution = Day6Solver.solve(input);
-        assert_eq!(solution.part1, "5");
-        assert_eq!(solution.part2, "11");
+impl Default for Day6Params {
+    fn default() -> Self {
+        Self {
+            distance: 1000,
+            repeats: 1000,
+        }
+    }
+}
+
+impl Params for Day6Params {
+    const KEYS: &'static [&'static str] = &["distance", "repeats"];
+
+    fn set(&mut self, key: &str, value: &str) -> Result<(), SolveError> {
+        match key {
+            "distance" => self.distance = parse_value(key, value)?,
+            _ => self.repeats = parse_value(key, value)?,
+        }
+        Ok(())
     }
 }
@@ -2,51 +2,63 @@ use std::collections::{HashMap, HashSet};
 
 use regex::Regex;
 
-use crate::solutions::solution;
+use crate::solutions::Registration;
+use crate::solutions::solution::{self, Answer, Part, PuzzleParams, SolveError};
 
 pub struct Day7Solver;
 
+pub const REGISTRATION: Registration = Registration {
+    day: 7,
+    title: "Namegraph",
+    parts: &[Part::One, Part::Two, Part::Three],
+    solver: &Day7Solver,
+};
+
 impl solution::Solver for Day7Solver {
-    fn solve(&self, input: solution::Input) -> solution::Solution {
-        solution::Solution {
-            part1: part1(&input.part1),
-            part2: part2(&input.part2).to_string(),
-            part3: part3(&input.part3).to_string(),
-        }
+    fn part1(&self, input: &str, _params: &PuzzleParams) -> Result<Answer, SolveError> {
+        part1(input).map(Answer::from)
+    }
+
+    fn part2(&self, input: &str, _params: &PuzzleParams) -> Result<Answer, SolveError> {
+        Ok(part2(input)?.into())
+    }
+
+    fn part3(&self, input: &str, _params: &PuzzleParams) -> Result<Answer, SolveError> {
+        Ok(part3(input)?.into())
     }
 }
 
 type Rules = HashMap<char, HashSet<char>>;
 
-fn part1(input: &str) -> String {
-    let (names, rules) = parse(input);
+fn part1(input: &str) -> Result<String, SolveError> {
+    let (names, rules) = solution::parsed(parse(input)?);
 
     names
         .iter()
         .find(|name| matches_rules(name, &rules))
-        .unwrap()
-        .to_string()
+        .map(|name| name.to_string())
+        .ok_or_else(|| SolveError::no_answer("no name matches the rules"))
 }
 
-fn part2(input: &str) -> u64 {
-    let (names, rules) = parse(input);
+fn part2(input: &str) -> Result<u64, SolveError> {
+    let (names, rules) = solution::parsed(parse(input)?);
 
-    names
+    Ok(names
         .iter()
         .enumerate()
         .filter(|(_i, name)| matches_rules(name, &rules))
         .map(|(idx, _name)| (idx + 1) as u64)
-        .sum()
+        .sum())
 }
 
-fn part3(input: &str) -> u64 {
-    let (prefixes, rules) = parse(input);
+fn part3(input: &str) -> Result<u64, SolveError> {
+    let (prefixes, rules) = solution::parsed(parse(input)?);
 
-    remove_substrings(&prefixes)
+    Ok(remove_substrings(&prefixes)
         .iter()
         .filter(|name| matches_rules(name, &rules))
         .map(|name| combinations(name, &rules))
-        .sum()
+        .sum())
 }
 
 fn combinations(name: &str, rules: &Rules) -> u64 {
@@ -57,7 +69,9 @@ fn combinations(name: &str, rules: &Rules) -> u64 {
         return 1;
     }
 
-    let last_char = name.chars().last().unwrap();
+    let Some(last_char) = name.chars().last() else {
+        return 0;
+    };
     let default = HashSet::new();
     let nexts = rules.get(&last_char).unwrap_or(&default);
     let to_add = if (7..=10).contains(&length) { 1 } else { 0 };
@@ -84,7 +98,10 @@ fn remove_substrings(names: &[&str]) -> Vec<String> {
 
 fn matches_rules(name: &str, rules: &Rules) -> bool {
     for (l, r) in name.chars().zip(name.chars().skip(1)) {
-        if !rules.get(&l).unwrap().contains(&r) {
+        if !rules
+            .get(&l)
+            .is_some_and(|followers| followers.contains(&r))
+        {
             return false;
         }
     }
@@ -92,105 +109,31 @@ fn matches_rules(name: &str, rules: &Rules) -> bool {
     true
 }
 
-fn parse(input: &str) -> (Vec<&str>, Rules) {
-    let mut i = input.split("\n\n");
-    let names: Vec<&str> = i.next().unwrap().split(",").collect();
-    let rules = parse_rules(i.next().unwrap());
+fn parse(input: &str) -> Result<(Vec<&str>, Rules), SolveError> {
+    let (names, rules) = input.split_once("\n\n").ok_or_else(|| {
+        SolveError::parse(input, "expected names and rules separated by a blank line")
+    })?;
+    let names: Vec<&str> = names.split(",").collect();
+    let rules = parse_rules(rules)?;
 
-    (names, rules)
+    Ok((names, rules))
 }
 
-fn parse_rules(rules: &str) -> Rules {
-    let regex = Regex::new(r"(?P<lead>\w)\s>\s(?P<followers>[\w,]+)").unwrap();
+fn parse_rules(rules: &str) -> Result<Rules, SolveError> {
+    let regex = Regex::new(r"^(?P<lead>\w)\s>\s(?P<followers>\w(,\w)*)$").unwrap();
 
     rules
         .split("\n")
         .map(|line| {
-            let capts = regex.captures(line).unwrap();
+            let capts = regex
+                .captures(line)
+                .ok_or_else(|| SolveError::parse(line, "expected a rule like 'r > a,i,o'"))?;
             let lead = capts["lead"].chars().next().unwrap();
             let followers = capts["followers"]
                 .split(",")
-                .map(|c| c.chars().next().unwrap());
+                .filter_map(|c| c.chars().next());
 
-            (lead, HashSet::from_iter(followers))
+            Ok((lead, HashSet::from_iter(followers)))
         })
         .collect()
 }
-
-#[cfg(test)]
-mod tests {
-    use super::solution::Solver;
-    use super::*;
-    #[test]
-    fn test_input() {
-        let input_1 = r#"Oronris,Urakris,Oroneth,Uraketh
-
-r > a,i,o
-i > p,w
-n > e,r
-o > n,m
-k > f,r
-a > k
-U > r
-e > t
-O > r
-t > h"#;
-
-        let input_2 = r#"Xanverax,Khargyth,Nexzeth,Helther,Braerex,Tirgryph,Kharverax
-
-r > v,e,a,g,y
-a > e,v,x,r
-e > r,x,v,t
-h > a,e,v
-g > r,y
-y > p,t
-i > v,r
-K > h
-v > e
-B > r
-t > h
-N > e
-p > h
-H > e
-l > t
-z > e
-X > a
-n > v
-x > z
-T > i"#;
-
-        let input_3 = r#"Khara,Xaryt,Noxer,Kharax
-
-r > v,e,a,g,y
-a > e,v,x,r,g
-e > r,x,v,t
-h > a,e,v
-g > r,y
-y > p,t
-i > v,r
-K > h
-v > e
-B > r
-t > h
-N > e
-p > h
-H > e
-l > t
-z > e
-X > a
-n > v
-x > z
-T > i"#;
-
-        let input = solution::Input {
-            part1: input_1.into(),
-            part2: input_2.into(),
-            part3: input_3.into(),
-        };
-
-        let solution = Day7Solver.solve(input);
-        assert_eq!(solution.part1, "Oroneth");
-        assert_eq!(solution.part2, "23");
-        assert_eq!(solution.part3, "1154");
-    }
-}
@@ -1,104 +1,273 @@
-use crate::solutions::solution;
+use crate::solutions::Registration;
+use crate::solutions::chord::{self, Chord};
+use crate::solutions::error::parse_number;
+use crate::solutions::params::{Params, parse_value};
+use crate::solutions::solution::{self, Answer, Image, Part, PuzzleParams, SolveError};
+use std::f64::consts::TAU;
+use std::fmt::Write;
 
 pub struct Day8Solver;
 
+pub const REGISTRATION: Registration = Registration {
+    day: 8,
+    title: "The Art of Connection",
+    parts: &[Part::One, Part::Two, Part::Three],
+    solver: &Day8Solver,
+};
+
 impl solution::Solver for Day8Solver {
-    fn solve(&self, input: solution::Input) -> solution::Solution {
-        solution::Solution {
-            part1: part1(&input.part1).to_string(),
-            part2: part2(&input.part2).to_string(),
-            part3: part3(&input.part3).to_string(),
-        }
+    fn part1(&self, input: &str, params: &PuzzleParams) -> Result<Answer, SolveError> {
+        let params = Day8Params::from_params(params)?;
+        Ok(part1(&solution::parsed(parse(input, params.points)?), &params).into())
+    }
+
+    fn part2(&self, input: &str, params: &PuzzleParams) -> Result<Answer, SolveError> {
+        let params = Day8Params::from_params(params)?;
+        Ok(part2(&solution::parsed(parse(input, params.points)?)).into())
     }
+
+    fn part3(&self, input: &str, params: &PuzzleParams) -> Result<Answer, SolveError> {
+        let params = Day8Params::from_params(params)?;
+        part3(&solution::parsed(parse(input, params.points)?), &params).map(Answer::from)
+    }
+
+    fn check_params(&self, _part: Part, params: &PuzzleParams) -> Result<(), SolveError> {
+        Day8Params::from_params(params).map(|_| ())
+    }
+
+    fn render(
+        &self,
+        part: Part,
+        input: &str,
+        params: &PuzzleParams,
+    ) -> Result<Option<Image>, SolveError> {
+        let points = Day8Params::from_params(params)?.points;
+        let threads = parse(input, points)?;
+        let strike = match part {
+            Part::Three => best_cut(&threads, points).map(|(_, chord)| chord),
+            _ => None,
+        };
+
+        Ok(Some(Image::Svg(svg(&threads, points, strike))))
+    }
+}
+
+/// The number of nails around the circle.
+struct Day8Params {
+    points: u16,
 }
 
-fn part1(input: &str) -> u64 {
-    let crosses = parse(input);
-    let points = points();
+fn part1(threads: &[Chord], params: &Day8Params) -> u64 {
+    let points = params.points;
 
-    crosses
+    threads
         .iter()
-        .filter(|(from, to)| (from.abs_diff(*to)) == (points / 2))
+        .filter(|thread| thread.to - thread.from == points / 2)
         .count() as u64
 }
 
-fn part2(input: &str) -> u64 {
-    let crosses = parse(input);
+fn part2(threads: &[Chord]) -> u64 {
+    chord::knots(threads)
+}
 
-    crosses
-        .iter()
-        .enumerate()
-        .map(|(idx, (x2, y2))| {
-            crosses[..idx]
-                .iter()
-                .filter(|(x1, y1)| {
-                    let (f1, t1) = (x1.min(y1), x1.max(y1));
-                    let (f, t) = (x2.min(y2), x2.max(y2));
-                    (f1 < f && f < t1 && t1 < t) || (f < f1 && f1 < t && t < t1)
-                })
-                .count() as u64
-        })
-        .sum()
+fn part3(threads: &[Chord], params: &Day8Params) -> Result<u64, SolveError> {
+    best_cut(threads, params.points)
+        .map(|(count, _)| count as u64)
+        .ok_or_else(|| SolveError::no_answer("a cut needs at least 2 nails"))
 }
 
-fn part3(input: &str) -> u64 {
-    let crosses = parse(input);
+/// The chord `(f, t)` with `f < t` crossing or matching the most threads, with the number of
+/// threads it cuts, or `None` when there are fewer than 2 nails.
+///
+/// A thread `(a, b)` with `a < b` is cut by every chord with `a < f < b < t` or
+/// `f < a < t < b`, two rectangles in the `(f, t)` plane, and by the chord `(a, b)` itself.
+/// Each thread adds its rectangles to a 2D difference array whose prefix sums then hold the
+/// count of every chord, in O(P² + N) for P nails and N threads. The threads must be strung
+/// between nails `1..=points`, as `parse` checks.
+fn best_cut(threads: &[Chord], points: u16) -> Option<(usize, Chord)> {
+    let n = points as usize + 2;
+    let mut diff = vec![0i32; n * n];
+    let mut add = |(f1, f2): (usize, usize), (t1, t2): (usize, usize)| {
+        if f1 > f2 || t1 > t2 {
+            return;
+        }
+        diff[f1 * n + t1] += 1;
+        diff[f1 * n + t2 + 1] -= 1;
+        diff[(f2 + 1) * n + t1] -= 1;
+        diff[(f2 + 1) * n + t2 + 1] += 1;
+    };
 
-    let mut max = 0;
+    for thread in threads {
+        let (a, b) = (thread.from as usize, thread.to as usize);
+        add((a + 1, b - 1), (b + 1, points as usize));
+        add((1, a - 1), (a + 1, b - 1));
+        add((a, a), (b, b));
+    }
 
-    for i in 1..points() {
-        for j in i + 1..=points() {
-            max = max.max(count_crosses(&(i, j), &crosses))
+    for f in 1..n {
+        for t in 1..n {
+            diff[f * n + t] +=
+                diff[(f - 1) * n + t] + diff[f * n + t - 1] - diff[(f - 1) * n + t - 1];
         }
     }
 
-    max as u64
+    let mut best = None;
+    for f in 1..points {
+        for t in f + 1..=points {
+            let count = diff[f as usize * n + t as usize] as usize;
+            if best.is_none_or(|(max, _)| count > max) {
+                best = Some((count, Chord::new(f, t)));
+            }
+        }
+    }
+    best
 }
 
-fn count_crosses(strike: &(u16, u16), crosses: &[(u16, u16)]) -> usize {
-    let (f, t) = strike;
-    crosses
+/// Draws the nails around a circle and the threads between them, with the `strike` in red
+/// and the threads it cuts in orange.
+fn svg(threads: &[Chord], points: u16, strike: Option<Chord>) -> String {
+    let nail = |n: u16| {
+        let angle = TAU * (n as f64 - 1.0) / points.max(1) as f64;
+        (100.0 * angle.sin(), -100.0 * angle.cos())
+    };
+    let line = |svg: &mut String, chord: &Chord, color: &str, width: f64| {
+        let ((x1, y1), (x2, y2)) = (nail(chord.from), nail(chord.to));
+        let _ = writeln!(
+            svg,
+            r#"<line x1="{x1:.2}" y1="{y1:.2}" x2="{x2:.2}" y2="{y2:.2}" stroke="{color}" stroke-width="{width}"/>"#
+        );
+    };
+
+    let mut svg = String::from(concat!(
+        r#"<svg xmlns="http://www.w3.org/2000/svg" viewBox="-110 -110 220 220">"#,
+        "\n",
+        r#"<rect x="-110" y="-110" width="220" height="220" fill="white"/>"#,
+        "\n",
+    ));
+
+    let (cut, uncut): (Vec<&Chord>, Vec<&Chord>) = threads
         .iter()
-        .filter(|(x1, y1)| {
-            let (f1, t1) = (x1.min(y1), x1.max(y1));
-            (f1 < f && f < t1 && t1 < t) || (f < f1 && f1 < t && t < t1) || (f == f1 && t == t1)
-        })
-        .count()
+        .partition(|thread| strike.is_some_and(|strike| strike.cuts(thread)));
+    for thread in uncut {
+        line(&mut svg, thread, "gray", 0.3);
+    }
+    for thread in cut {
+        line(&mut svg, thread, "orange", 0.6);
+    }
+    if let Some(strike) = strike {
+        line(&mut svg, &strike, "red", 1.0);
+    }
+
+    for n in 1..=points {
+        let (x, y) = nail(n);
+        let _ = writeln!(svg, r#"<circle cx="{x:.2}" cy="{y:.2}" r="0.8"/>"#);
+    }
+    svg + "</svg>\n"
 }
 
-fn parse(input: &str) -> Vec<(u16, u16)> {
-    let nums: Vec<u16> = input.split(",").map(|num| num.parse().unwrap()).collect();
+/// The threads strung between consecutive nails of the input, numbered from 1 to `points`.
+fn parse(input: &str, points: u16) -> Result<Vec<Chord>, SolveError> {
+    let nails: Vec<(&str, u16)> = input
+        .split(",")
+        .map(|fragment| match parse_number(fragment)? {
+            nail if (1..=points).contains(&nail) => Ok((fragment, nail)),
+            _ => Err(SolveError::parse(
+                fragment,
+                format!("expected a nail between 1 and {points}"),
+            )),
+        })
+        .collect::<Result<_, _>>()?;
 
-    nums.iter()
-        .zip(nums.iter().skip(1))
-        .map(|(x, y)| (*x, *y))
+    nails
+        .iter()
+        .zip(nails.iter().skip(1))
+        .map(|((_, from), (fragment, to))| match from == to {
+            true => Err(SolveError::parse(
+                fragment,
+                "a thread needs two different nails",
+            )),
+            false => Ok(Chord::new(*from, *to)),
+        })
         .collect()
 }
 
-fn points() -> u16 {
-    if cfg!(test) { 8 } else { 256 }
+impl Default for Day8Params {
+    fn default() -> Self {
+        Self { points: 256 }
+    }
+}
+
+impl Params for Day8Params {
+    const KEYS: &'static [&'static str] = &["points"];
+
+    fn set(&mut self, key: &str, value: &str) -> Result<(), SolveError> {
+        self.points = parse_value(key, value)?;
+        Ok(())
+    }
 }
 
 #[cfg(test)]
 mod tests {
-    use super::solution::Solver;
     use super::*;
+    use crate::solutions::testing::Lcg;
+
+    fn count_crosses(strike: &Chord, threads: &[Chord]) -> usize {
+        threads.iter().filter(|thread| strike.cuts(thread)).count()
+    }
 
     #[test]
-    fn test_example() {
-        let input_1 = "1,5,2,6,8,4,1,7,3";
-        let input_2 = "1,5,2,6,8,4,1,7,3,5,7,8,2";
-        let input_3 = "1,5,2,6,8,4,1,7,3";
-
-        let input = solution::Input {
-            part1: input_1.into(),
-            part2: input_2.into(),
-            part3: input_3.into(),
-        };
+    fn test_best_cut() {
+        let mut random = Lcg::new(7);
+        let mut next = |points: u16| random.below(points as usize) as u16 + 1;
+
+        for points in [2, 3, 8, 17, 32] {
+            let threads: Vec<Chord> = (0..60)
+                .map(|_| Chord::new(next(points), next(points)))
+                .collect();
+            let (max, chord) = best_cut(&threads, points).unwrap();
+
+            let brute = (1..points)
+                .flat_map(|f| (f + 1..=points).map(move |t| Chord::new(f, t)))
+                .map(|chord| count_crosses(&chord, &threads))
+                .max()
+                .unwrap();
+            assert_eq!(max, brute, "{points} nails");
+            assert_eq!(count_crosses(&chord, &threads), max);
+        }
+        assert_eq!(
+            best_cut(&[Chord::new(1, 5), Chord::new(5, 2)], 8),
+            Some((2, Chord::new(3, 6)))
+        );
+        assert!(best_cut(&[], 1).is_none());
+    }
+
+    #[test]
+    fn test_svg() {
+        let threads = parse("1,5,2", 8).unwrap();
+        let svg = svg(&threads, 8, Some(Chord::new(3, 6)));
+
+        assert!(svg.starts_with("<svg"));
+        assert_eq!(svg.matches("<circle").count(), 8);
+        assert_eq!(svg.matches(r#"stroke="orange""#).count(), 2);
+        assert_eq!(svg.matches(r#"stroke="red""#).count(), 1);
+        assert!(svg.contains(r#"<line x1="0.00" y1="-100.00" x2="0.00" y2="100.00""#));
+    }
 
-        let solution = Day8Solver.solve(input);
-        assert_eq!(solution.part1, "4");
-        assert_eq!(solution.part2, "21");
-        assert_eq!(solution.part3, "7");
+    #[test]
+    fn test_parse() {
+        assert_eq!(
+            parse("1,5,2", 8).unwrap(),
+            vec![Chord::new(1, 5), Chord::new(2, 5)]
+        );
+
+        for (input, message) in [
+            ("1,9,2", "between 1 and 8"),
+            ("0,5", "between 1 and 8"),
+            ("1,5,5", "two different nails"),
+        ] {
+            let err = parse(input, 8).unwrap_err().locate(input);
+            assert!(err.to_string().contains(message), "{input}: {err}");
+            assert!(err.to_string().contains("line 1"), "{input}: {err}");
+        }
     }
 }
@@ -1,144 +1,295 @@
-use crate::solutions::solution;
+use crate::solutions::Registration;
+use crate::solutions::error::parse_number;
+use crate::solutions::solution::{self, Answer, Part, PuzzleParams, SolveError};
 use disjoint::DisjointSet;
+use std::cmp::Reverse;
+use std::str::FromStr;
 
 pub struct Day9Solver;
 
+pub const REGISTRATION: Registration = Registration {
+    day: 9,
+    title: "Encoded in the Scales",
+    parts: &[Part::One, Part::Two, Part::Three],
+    solver: &Day9Solver,
+};
+
 impl solution::Solver for Day9Solver {
-    fn solve(&self, input: solution::Input) -> solution::Solution {
-        solution::Solution {
-            part1: part1(&input.part1).to_string(),
-            part2: part2(&input.part2).to_string(),
-            part3: part3(&input.part3).to_string(),
-        }
+    fn part1(&self, input: &str, _params: &PuzzleParams) -> Result<Answer, SolveError> {
+        Ok(part1(input)?.into())
+    }
+
+    fn part2(&self, input: &str, _params: &PuzzleParams) -> Result<Answer, SolveError> {
+        Ok(part2(input)?.into())
     }
+
+    fn part3(&self, input: &str, _params: &PuzzleParams) -> Result<Answer, SolveError> {
+        Ok(part3(input)?.into())
+    }
+}
+
+/// A scale and its identifier.
+#[derive(Debug)]
+struct Scale {
+    id: u64,
+    dna: DnaSequence,
 }
 
-type Dna = Vec<char>;
+/// A sequence of nucleotides packed 2 bits each, `LANES` to a word.
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub struct DnaSequence {
+    words: Vec<u64>,
+    len: usize,
+}
+
+/// Nucleotides packed in a word.
+const LANES: usize = 32;
+/// The low bit of every lane of a word.
+const LOW_BITS: u64 = 0x5555_5555_5555_5555;
 
-fn part1(input: &str) -> u64 {
-    let (child, parents) = parse1(input);
+fn part1(input: &str) -> Result<u64, SolveError> {
+    let (child, parents) = solution::parsed(parse1(input)?);
 
-    parents
+    Ok(parents
         .iter()
-        .map(|p| p.iter().zip(&child).filter(|(c1, c2)| *c1 == *c2).count() as u64)
-        .product()
+        .map(|parent| parent.dna.matches(&child.dna) as u64)
+        .product())
 }
 
-fn part2(input: &str) -> u64 {
-    let dnas = parse(input);
-    let mut total = 0;
+fn part2(input: &str) -> Result<u64, SolveError> {
+    let scales = solution::parsed(parse(input)?);
 
-    for (ic, child) in dnas.iter().enumerate() {
-        for (ip1, p1) in dnas.iter().enumerate() {
-            for (ip2, p2) in dnas.iter().enumerate() {
-                if ic == ip1 || ic == ip2 || ip1 <= ip2 {
-                    continue;
-                }
-                if is_child(child, p1, p2) {
-                    total += similarity(child, p1, p2);
-                }
-            }
-        }
+    Ok(families(&scales)
+        .iter()
+        .map(|family| {
+            let (p1, p2) = family.parents;
+            let child = &scales[family.child].dna;
+            (child.matches(&scales[p1].dna) * child.matches(&scales[p2].dna)) as u64
+        })
+        .sum())
+}
+
+fn part3(input: &str) -> Result<u64, SolveError> {
+    let scales = solution::parsed(parse(input)?);
+    let mut groups = DisjointSet::with_len(scales.len());
+
+    for family in families(&scales) {
+        let (p1, p2) = family.parents;
+        groups.join(family.child, p1);
+        groups.join(family.child, p2);
     }
 
-    total
+    groups
+        .sets()
+        .iter()
+        .max_by(|f1, f2| f1.len().cmp(&f2.len()))
+        .map(|values| values.iter().map(|idx| scales[*idx].id).sum())
+        .ok_or_else(|| SolveError::no_answer("there are no scales"))
+}
+
+/// A child and the two sequences it was bred from, as indices into the scales.
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+struct Family {
+    child: usize,
+    parents: (usize, usize),
 }
 
-fn part3(input: &str) -> u64 {
-    let dnas = parse(input);
-    let mut families = DisjointSet::with_len(dnas.len());
+/// Finds every child whose nucleotides all come from one of two other sequences.
+///
+/// As the matches of both parents must add up to the length at least, the candidates are
+/// tried from the best matching one and a pair is skipped as soon as they fall short.
+fn families(scales: &[Scale]) -> Vec<Family> {
+    let len = scales.first().map_or(0, |scale| scale.dna.len);
+    let mut families = vec![];
+
+    for (child, c) in scales.iter().enumerate() {
+        let mut candidates: Vec<(usize, usize)> = scales
+            .iter()
+            .enumerate()
+            .filter(|(parent, _)| *parent != child)
+            .map(|(parent, p)| (parent, c.dna.matches(&p.dna)))
+            .collect();
+        candidates.sort_by_key(|(_, count)| Reverse(*count));
 
-    for (ic, child) in dnas.iter().enumerate() {
-        for (ip1, p1) in dnas.iter().enumerate() {
-            for (ip2, p2) in dnas.iter().enumerate() {
-                if ic == ip1 || ic == ip2 || ip1 <= ip2 {
-                    continue;
+        for (idx, (p1, n1)) in candidates.iter().enumerate() {
+            for (p2, n2) in &candidates[idx + 1..] {
+                if n1 + n2 < len {
+                    break;
                 }
-                if is_child(child, p1, p2) {
-                    families.join(ic, ip1);
-                    families.join(ic, ip2);
+                if c.dna.is_child_of(&scales[*p1].dna, &scales[*p2].dna) {
+                    families.push(Family {
+                        child,
+                        parents: (*p1.max(p2), *p1.min(p2)),
+                    });
                 }
             }
         }
     }
 
     families
-        .sets()
-        .iter()
-        .max_by(|f1, f2| f1.len().cmp(&f2.len()))
-        .map(|values| values.iter().map(|x| x + 1).sum::<usize>())
-        .unwrap() as u64
 }
 
-fn is_child(child: &Dna, p1: &Dna, p2: &Dna) -> bool {
-    child
-        .iter()
-        .enumerate()
-        .all(|(idx, c)| *c == p1[idx] || *c == p2[idx])
+impl DnaSequence {
+    /// The number of positions holding the same nucleotide in both sequences.
+    pub fn matches(&self, other: &DnaSequence) -> usize {
+        (0..self.words.len())
+            .map(|word| self.match_mask(other, word).count_ones() as usize)
+            .sum()
+    }
+
+    /// Whether every nucleotide of the sequence comes from `p1` or `p2`, i.e. the matches of
+    /// the parents cover every lane of every word.
+    pub fn is_child_of(&self, p1: &DnaSequence, p2: &DnaSequence) -> bool {
+        (0..self.words.len()).all(|word| {
+            self.match_mask(p1, word) | self.match_mask(p2, word) == self.full_mask(word)
+        })
+    }
+
+    /// Sets the low bit of the lanes of a word where both sequences hold the same nucleotide.
+    fn match_mask(&self, other: &DnaSequence, word: usize) -> u64 {
+        let diff = self.words[word] ^ other.words.get(word).copied().unwrap_or(!0);
+        !(diff | (diff >> 1)) & self.full_mask(word)
+    }
+
+    /// The low bit of every lane of a word holding a nucleotide.
+    fn full_mask(&self, word: usize) -> u64 {
+        match self.len - word * LANES {
+            lanes if lanes >= LANES => LOW_BITS,
+            lanes => LOW_BITS & ((1 << (2 * lanes)) - 1),
+        }
+    }
+}
+
+impl FromStr for DnaSequence {
+    type Err = SolveError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut words = vec![0; s.len().div_ceil(LANES)];
+
+        for (idx, c) in s.char_indices() {
+            let code = match c {
+                'A' => 0,
+                'C' => 1,
+                'G' => 2,
+                'T' => 3,
+                _ => {
+                    return Err(SolveError::parse(
+                        &s[idx..idx + c.len_utf8()],
+                        "invalid base, expected A, C, G or T",
+                    ));
+                }
+            };
+            words[idx / LANES] |= code << (2 * (idx % LANES));
+        }
+
+        Ok(Self {
+            words,
+            len: s.len(),
+        })
+    }
 }
 
-fn similarity(child: &Dna, p1: &Dna, p2: &Dna) -> u64 {
-    [p1, p2]
+fn parse(input: &str) -> Result<Vec<Scale>, SolveError> {
+    let scales: Vec<Scale> = input.lines().map(parse_scale).collect::<Result<_, _>>()?;
+
+    match scales
         .iter()
-        .map(|p| p.iter().zip(child).filter(|(c1, c2)| *c1 == *c2).count() as u64)
-        .product()
+        .zip(input.lines())
+        .find(|(scale, _)| scale.dna.len != scales[0].dna.len)
+    {
+        Some((_, line)) => Err(SolveError::parse(
+            line,
+            "all sequences must have the same length",
+        )),
+        None => Ok(scales),
+    }
 }
 
-fn parse(input: &str) -> Vec<Dna> {
-    input
-        .lines()
-        .map(|l| l.split(":").nth(1).unwrap().chars().collect())
-        .collect()
+fn parse1(input: &str) -> Result<(Scale, Vec<Scale>), SolveError> {
+    let mut scales = parse(input)?;
+    let child = scales
+        .pop()
+        .ok_or_else(|| SolveError::parse(input, "expected at least one sequence"))?;
+
+    Ok((child, scales))
 }
 
-fn parse1(input: &str) -> (Dna, Vec<Dna>) {
-    let mut dnas = input
-        .lines()
-        .rev()
-        .map(|l| l.split(":").nth(1).unwrap().chars().collect::<Vec<char>>());
-    let child = dnas.next().unwrap();
+fn parse_scale(line: &str) -> Result<Scale, SolveError> {
+    let (id, dna) = line
+        .split_once(":")
+        .ok_or_else(|| SolveError::parse(line, "expected a sequence like '1:ACGT'"))?;
 
-    (child, dnas.collect())
+    Ok(Scale {
+        id: parse_number(id)?,
+        dna: dna.parse()?,
+    })
 }
 
 #[cfg(test)]
 mod tests {
-
-    use super::solution::Solver;
     use super::*;
+    use crate::solutions::testing::Lcg;
+
+    fn is_child(child: &[u8], p1: &[u8], p2: &[u8]) -> bool {
+        child
+            .iter()
+            .enumerate()
+            .all(|(idx, c)| *c == p1[idx] || *c == p2[idx])
+    }
 
     #[test]
-    fn test_input() {
-        let input_1 = r#"1:CAAGCGCTAAGTTCGCTGGATGTGTGCCCGCG
-2:CTTGAATTGGGCCGTTTACCTGGTTTAACCAT
-3:CTAGCGCTGAGCTGGCTGCCTGGTTGACCGCG"#;
-
-        let input_2 = r#"1:GCAGGCGAGTATGATACCCGGCTAGCCACCCC
-2:TCTCGCGAGGATATTACTGGGCCAGACCCCCC
-3:GGTGGAACATTCGAAAGTTGCATAGGGTGGTG
-4:GCTCGCGAGTATATTACCGAACCAGCCCCTCA
-5:GCAGCTTAGTATGACCGCCAAATCGCGACTCA
-6:AGTGGAACCTTGGATAGTCTCATATAGCGGCA
-7:GGCGTAATAATCGGATGCTGCAGAGGCTGCTG"#;
-
-        let input_3 = r#"1:GCAGGCGAGTATGATACCCGGCTAGCCACCCC
-2:TCTCGCGAGGATATTACTGGGCCAGACCCCCC
-3:GGTGGAACATTCGAAAGTTGCATAGGGTGGTG
-4:GCTCGCGAGTATATTACCGAACCAGCCCCTCA
-5:GCAGCTTAGTATGACCGCCAAATCGCGACTCA
-6:AGTGGAACCTTGGATAGTCTCATATAGCGGCA
-7:GGCGTAATAATCGGATGCTGCAGAGGCTGCTG
-8:GGCGTAAAGTATGGATGCTGGCTAGGCACCCG"#;
-
-        let input = solution::Input {
-            part1: input_1.into(),
-            part2: input_2.into(),
-            part3: input_3.into(),
-        };
-
-        let solution = Day9Solver.solve(input);
-        assert_eq!(solution.part1, "414");
-        assert_eq!(solution.part2, "1245");
-        assert_eq!(solution.part3, "36")
+    fn test_families() {
+        let missed = parse("1:AAAC\n2:AAAG\n3:AAAA").unwrap();
+        assert!(families(&missed).is_empty());
+
+        let mut random = Lcg::new(3);
+        let mut dnas: Vec<Vec<u8>> = (0..12)
+            .map(|_| (0..40).map(|_| b"ACGT"[random.below(4)]).collect())
+            .collect();
+        for _ in 0..8 {
+            let (p1, p2) = (random.below(dnas.len()), random.below(dnas.len()));
+            let child = (0..40)
+                .map(|idx| [&dnas[p1], &dnas[p2]][random.below(2)][idx])
+                .collect();
+            dnas.push(child);
+        }
+
+        let mut expected = vec![];
+        for (child, c) in dnas.iter().enumerate() {
+            for p1 in 0..dnas.len() {
+                for p2 in 0..p1 {
+                    if child != p1 && child != p2 && is_child(c, &dnas[p1], &dnas[p2]) {
+                        expected.push(Family {
+                            child,
+                            parents: (p1, p2),
+                        });
+                    }
+                }
+            }
+        }
+        let input: Vec<String> = dnas
+            .iter()
+            .enumerate()
+            .map(|(idx, dna)| format!("{}:{}", idx + 1, String::from_utf8_lossy(dna)))
+            .collect();
+        let mut found = families(&parse(&input.join("\n")).unwrap());
+        found.sort_by_key(|family| (family.child, family.parents));
+
+        assert!(expected.len() >= 8);
+        assert_eq!(found, expected);
+    }
+
+    #[test]
+    fn test_dna_sequence() {
+        let dna: DnaSequence = "ACGTTGCA".parse().unwrap();
+
+        assert_eq!(dna.matches(&"ACGAAGCA".parse().unwrap()), 6);
+        assert!(dna.is_child_of(&"ACGAAAAA".parse().unwrap(), &"AAATTGCA".parse().unwrap()));
+        assert!(!dna.is_child_of(&"ACGAAAAA".parse().unwrap(), &"AAAATGCA".parse().unwrap()));
+
+        let input = "1:ACGT\n2:ACXT";
+        let err = parse(input).unwrap_err().locate(input);
+        assert!(err.to_string().contains("invalid base"));
+        assert_eq!(parse1("7:AC\n42:CA").unwrap().0.id, 42);
     }
 }
@@ -0,0 +1,185 @@
+use std::fmt;
+use std::io;
+use std::path::PathBuf;
+use std::str::FromStr;
+
+#[derive(Debug)]
+pub enum SolveError {
+    Io { path: PathBuf, source: io::Error },
+    Parse(ParseError),
+    NoAnswer(String),
+    Overflow(String),
+    Param(String),
+    Fetch(String),
+    Submit(String),
+    Scaffold(String),
+    Render(String),
+}
+
+#[derive(Debug)]
+pub struct ParseError {
+    pub message: String,
+    pub snippet: String,
+    pub location: Option<Location>,
+    address: usize,
+}
+
+#[derive(Debug, PartialEq, Eq)]
+pub struct Location {
+    pub line: usize,
+    pub column: usize,
+    pub source_line: String,
+}
+
+impl SolveError {
+    /// Creates a parse error for `fragment`, which should be a slice of the puzzle input
+    /// so that `locate` can later resolve its line and column.
+    pub fn parse(fragment: &str, message: impl Into<String>) -> Self {
+        SolveError::Parse(ParseError {
+            message: message.into(),
+            snippet: fragment.to_string(),
+            location: None,
+            address: fragment.as_ptr() as usize,
+        })
+    }
+
+    pub fn no_answer(message: impl Into<String>) -> Self {
+        SolveError::NoAnswer(message.into())
+    }
+
+    pub fn overflow(message: impl Into<String>) -> Self {
+        SolveError::Overflow(message.into())
+    }
+
+    pub fn param(message: impl Into<String>) -> Self {
+        SolveError::Param(message.into())
+    }
+
+    pub fn fetch(message: impl Into<String>) -> Self {
+        SolveError::Fetch(message.into())
+    }
+
+    pub fn submit(message: impl Into<String>) -> Self {
+        SolveError::Submit(message.into())
+    }
+
+    pub fn scaffold(message: impl Into<String>) -> Self {
+        SolveError::Scaffold(message.into())
+    }
+
+    pub fn render(message: impl Into<String>) -> Self {
+        SolveError::Render(message.into())
+    }
+
+    /// Resolves the line and column of a parse error relative to the input it was parsed from.
+    pub fn locate(self, input: &str) -> Self {
+        match self {
+            SolveError::Parse(mut e) if e.location.is_none() => {
+                let start = input.as_ptr() as usize;
+                if (start..=start + input.len()).contains(&e.address) {
+                    e.location = Some(Location::at(input, e.address - start));
+                }
+                SolveError::Parse(e)
+            }
+            other => other,
+        }
+    }
+}
+
+impl Location {
+    fn at(input: &str, offset: usize) -> Self {
+        let before = &input[..offset];
+        let line_start = before.rfind('\n').map_or(0, |idx| idx + 1);
+        let line_end = input[offset..]
+            .find('\n')
+            .map_or(input.len(), |idx| offset + idx);
+
+        Self {
+            line: before.matches('\n').count() + 1,
+            column: before[line_start..].chars().count() + 1,
+            source_line: input[line_start..line_end].to_string(),
+        }
+    }
+}
+
+/// Parses `fragment` as a number, reporting it as the offending snippet on failure.
+pub fn parse_number<T: FromStr>(fragment: &str) -> Result<T, SolveError> {
+    fragment
+        .parse()
+        .map_err(|_| SolveError::parse(fragment, "invalid number"))
+}
+
+impl fmt::Display for SolveError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            SolveError::Io { path, source } => {
+                write!(f, "could not read '{}': {}", path.display(), source)
+            }
+            SolveError::Parse(e) => write!(f, "{e}"),
+            SolveError::NoAnswer(message) => write!(f, "no answer found: {message}"),
+            SolveError::Overflow(message) => write!(f, "arithmetic overflow: {message}"),
+            SolveError::Param(message) => write!(f, "{message}"),
+            SolveError::Fetch(message) => write!(f, "could not fetch the input: {message}"),
+            SolveError::Submit(message) => write!(f, "not submitted: {message}"),
+            SolveError::Scaffold(message) => write!(f, "could not scaffold the day: {message}"),
+            SolveError::Render(message) => write!(f, "could not render: {message}"),
+        }
+    }
+}
+
+impl fmt::Display for ParseError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match &self.location {
+            None => write!(f, "parse error: {} at '{}'", self.message, self.snippet),
+            Some(location) => {
+                let gutter = " ".repeat(location.line.to_string().len());
+                let snippet = self.snippet.lines().next().unwrap_or_default();
+                let underline = "^".repeat(snippet.chars().count().max(1));
+
+                writeln!(
+                    f,
+                    "parse error at line {}, column {}: {}",
+                    location.line, location.column, self.message
+                )?;
+                writeln!(f, "{gutter} |")?;
+                writeln!(f, "{} | {}", location.line, location.source_line)?;
+                write!(
+                    f,
+                    "{gutter} | {}{underline}",
+                    " ".repeat(location.column - 1)
+                )
+            }
+        }
+    }
+}
+
+impl std::error::Error for SolveError {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_locate() {
+        let input = "1,2,3\n4,x,6";
+        let fragment = &input[8..9];
+
+        let error = SolveError::parse(fragment, "invalid number").locate(input);
+        let SolveError::Parse(e) = error else {
+            panic!("expected a parse error");
+        };
+
+        assert_eq!(
+            e.location,
+            Some(Location {
+                line: 2,
+                column: 3,
+                source_line: "4,x,6".into()
+            })
+        );
+        assert_eq!(
+            e.to_string(),
+            "parse error at line 2, column 3: invalid number\n  |\n2 | 4,x,6\n  |   ^"
+        );
+    }
+}
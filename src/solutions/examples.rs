@@ -0,0 +1,24 @@
+// The tests of the `examples/{day}/{part}.txt` fixtures, generated by `build.rs`.
+
+use crate::input::normalize;
+use crate::solutions::find;
+use crate::solutions::params::Param;
+use crate::solutions::solution::{Part, PuzzleParams};
+
+fn run_example(day: u8, part: u8, input: &str, expected: &str, params: &[&str]) {
+    let registration = find(day).unwrap_or_else(|| panic!("day {day} is not registered"));
+    let part: Part = part.to_string().parse().unwrap();
+    let params: Vec<Param> = params.iter().map(|param| param.parse().unwrap()).collect();
+    let params = PuzzleParams::from(params);
+    let input = normalize(input.to_string());
+
+    registration.solver.check_params(part, &params).unwrap();
+    let answer = registration
+        .solver
+        .part(part, &input, &params)
+        .unwrap_or_else(|e| panic!("{e}"));
+
+    assert_eq!(answer, expected.trim());
+}
+
+include!(concat!(env!("OUT_DIR"), "/examples.rs"));
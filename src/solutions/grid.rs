@@ -0,0 +1,230 @@
+use crate::solutions::error::SolveError;
+use std::fmt;
+use std::ops::{Index, IndexMut};
+
+/// A `(row, column)` position, signed so that neighbors of the border can be computed
+/// before being bounds-checked.
+pub type Point = (i64, i64);
+
+#[allow(dead_code)]
+pub const ORTHOGONAL: [Point; 4] = [(-1, 0), (0, 1), (1, 0), (0, -1)];
+#[allow(dead_code)]
+pub const DIAGONAL: [Point; 4] = [(-1, -1), (-1, 1), (1, 1), (1, -1)];
+pub const KNIGHT: [Point; 8] = [
+    (-2, -1),
+    (-2, 1),
+    (-1, -2),
+    (-1, 2),
+    (1, -2),
+    (1, 2),
+    (2, -1),
+    (2, 1),
+];
+
+/// A dense, row-major 2D grid.
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub struct Grid<T> {
+    cells: Vec<T>,
+    rows: usize,
+    cols: usize,
+}
+
+impl<T> Grid<T> {
+    #[allow(dead_code)]
+    pub fn new(rows: usize, cols: usize, fill: T) -> Self
+    where
+        T: Clone,
+    {
+        Self {
+            cells: vec![fill; rows * cols],
+            rows,
+            cols,
+        }
+    }
+
+    /// Builds a grid from rows, which must all have the same length.
+    pub fn from_rows(rows: Vec<Vec<T>>) -> Self {
+        let cols = rows.first().map_or(0, Vec::len);
+        assert!(
+            rows.iter().all(|row| row.len() == cols),
+            "rows of differing lengths"
+        );
+
+        Self {
+            rows: rows.len(),
+            cols,
+            cells: rows.into_iter().flatten().collect(),
+        }
+    }
+
+    /// Parses a character map line by line, mapping every character with `cell`.
+    /// Characters rejected by `cell` and rows of differing lengths are parse errors.
+    pub fn parse(input: &str, cell: impl Fn(char) -> Option<T>) -> Result<Self, SolveError> {
+        let mut cells = vec![];
+        let mut rows = 0;
+        let mut cols = None;
+
+        for line in input.lines() {
+            let before = cells.len();
+            for (idx, c) in line.char_indices() {
+                let value = cell(c).ok_or_else(|| {
+                    SolveError::parse(
+                        &line[idx..idx + c.len_utf8()],
+                        format!("unexpected character '{c}'"),
+                    )
+                })?;
+                cells.push(value);
+            }
+
+            let width = cells.len() - before;
+            match cols {
+                Some(cols) if cols != width => {
+                    return Err(SolveError::parse(
+                        line,
+                        format!("expected a row of {cols} cells, found {width}"),
+                    ));
+                }
+                _ => cols = Some(width),
+            }
+            rows += 1;
+        }
+
+        Ok(Self {
+            cells,
+            rows,
+            cols: cols.unwrap_or(0),
+        })
+    }
+
+    pub fn rows(&self) -> usize {
+        self.rows
+    }
+
+    pub fn cols(&self) -> usize {
+        self.cols
+    }
+
+    pub fn contains(&self, (row, col): Point) -> bool {
+        (0..self.rows as i64).contains(&row) && (0..self.cols as i64).contains(&col)
+    }
+
+    pub fn get(&self, point: Point) -> Option<&T> {
+        self.index_of(point).map(|idx| &self.cells[idx])
+    }
+
+    pub fn get_mut(&mut self, point: Point) -> Option<&mut T> {
+        self.index_of(point).map(|idx| &mut self.cells[idx])
+    }
+
+    /// Every position of the grid with its cell, in row-major order.
+    pub fn iter(&self) -> impl Iterator<Item = (Point, &T)> {
+        let cols = self.cols.max(1);
+        self.cells
+            .iter()
+            .enumerate()
+            .map(move |(idx, cell)| (((idx / cols) as i64, (idx % cols) as i64), cell))
+    }
+
+    /// The first position, in row-major order, whose cell matches `predicate`.
+    pub fn find(&self, predicate: impl Fn(&T) -> bool) -> Option<Point> {
+        self.iter()
+            .find(|(_, cell)| predicate(cell))
+            .map(|(point, _)| point)
+    }
+
+    /// The positions reached from `point` by each of `offsets` that are inside the grid.
+    pub fn neighbors<'a>(
+        &'a self,
+        (row, col): Point,
+        offsets: &'a [Point],
+    ) -> impl Iterator<Item = Point> + 'a {
+        offsets
+            .iter()
+            .map(move |(dr, dc)| (row + dr, col + dc))
+            .filter(|point| self.contains(*point))
+    }
+
+    #[allow(dead_code)]
+    pub fn orthogonal(&self, point: Point) -> impl Iterator<Item = Point> + '_ {
+        self.neighbors(point, &ORTHOGONAL)
+    }
+
+    #[allow(dead_code)]
+    pub fn diagonal(&self, point: Point) -> impl Iterator<Item = Point> + '_ {
+        self.neighbors(point, &DIAGONAL)
+    }
+
+    pub fn knight(&self, point: Point) -> impl Iterator<Item = Point> + '_ {
+        self.neighbors(point, &KNIGHT)
+    }
+
+    fn index_of(&self, (row, col): Point) -> Option<usize> {
+        self.contains((row, col))
+            .then(|| row as usize * self.cols + col as usize)
+    }
+}
+
+impl<T> Index<Point> for Grid<T> {
+    type Output = T;
+
+    fn index(&self, point: Point) -> &T {
+        self.get(point)
+            .unwrap_or_else(|| panic!("{point:?} is outside of the grid"))
+    }
+}
+
+impl<T> IndexMut<Point> for Grid<T> {
+    fn index_mut(&mut self, point: Point) -> &mut T {
+        self.get_mut(point)
+            .unwrap_or_else(|| panic!("{point:?} is outside of the grid"))
+    }
+}
+
+/// Prints one line per row, with the cells next to each other.
+impl<T: fmt::Display> fmt::Display for Grid<T> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        for row in self.cells.chunks(self.cols.max(1)) {
+            for cell in row {
+                write!(f, "{cell}")?;
+            }
+            writeln!(f)?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_grid() {
+        let input = "#.#\n.S.";
+        let grid = Grid::parse(input, |c| matches!(c, '#' | '.' | 'S').then_some(c)).unwrap();
+
+        assert_eq!((grid.rows(), grid.cols()), (2, 3));
+        assert_eq!(grid.find(|c| *c == 'S'), Some((1, 1)));
+        assert_eq!(grid.get((0, 3)), None);
+        assert_eq!(grid[(0, 2)], '#');
+        assert_eq!(grid.to_string(), "#.#\n.S.\n");
+        assert_eq!(
+            grid.orthogonal((0, 0)).collect::<Vec<_>>(),
+            vec![(0, 1), (1, 0)]
+        );
+        assert_eq!(
+            grid.diagonal((1, 1)).collect::<Vec<_>>(),
+            vec![(0, 0), (0, 2)]
+        );
+        assert_eq!(grid.knight((0, 0)).collect::<Vec<_>>(), vec![(1, 2)]);
+
+        let err = Grid::parse(input, |c| (c != 'S').then_some(c))
+            .unwrap_err()
+            .locate(input);
+        assert!(err.to_string().contains("unexpected character 'S'"));
+        assert!(Grid::parse("..\n.", Some).is_err());
+
+        let mut blank = Grid::new(2, 2, '.');
+        blank[(1, 0)] = '#';
+        assert_eq!(blank.to_string(), "..\n#.\n");
+    }
+}
@@ -1,3 +1,7 @@
+use solution::{Part, Solver};
+
+pub mod answer;
+pub mod chord;
 pub mod day1;
 pub mod day10;
 pub mod day2;
@@ -8,4 +12,60 @@ pub mod day6;
 pub mod day7;
 pub mod day8;
 pub mod day9;
+pub mod error;
+#[cfg(test)]
+mod examples;
+pub mod grid;
+pub mod parallel;
+pub mod params;
 pub mod solution;
+#[cfg(test)]
+mod testing;
+
+/// Metadata of a day's solver, exported by every `dayN` module as `REGISTRATION`.
+pub struct Registration {
+    pub day: u8,
+    pub title: &'static str,
+    pub parts: &'static [Part],
+    pub solver: &'static dyn Solver,
+}
+
+pub const SOLVERS: &[Registration] = &[
+    day1::REGISTRATION,
+    day2::REGISTRATION,
+    day3::REGISTRATION,
+    day4::REGISTRATION,
+    day5::REGISTRATION,
+    day6::REGISTRATION,
+    day7::REGISTRATION,
+    day8::REGISTRATION,
+    day9::REGISTRATION,
+    day10::REGISTRATION,
+];
+
+pub fn find(day: u8) -> Option<&'static Registration> {
+    SOLVERS.iter().find(|r| r.day == day)
+}
+
+pub fn available_days() -> String {
+    SOLVERS
+        .iter()
+        .map(|r| r.day.to_string())
+        .collect::<Vec<String>>()
+        .join(", ")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_registry() {
+        let days: Vec<u8> = SOLVERS.iter().map(|r| r.day).collect();
+
+        assert!(days.is_sorted_by(|a, b| a < b));
+        assert!(SOLVERS.iter().all(|r| !r.parts.is_empty()));
+        assert_eq!(find(3).map(|r| r.title), Some("The Deepest Fit"));
+        assert!(find(42).is_none());
+    }
+}
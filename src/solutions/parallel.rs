@@ -0,0 +1,162 @@
+use std::panic;
+use std::sync::OnceLock;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::thread;
+
+/// Maps `items` with `f` and returns the results in the order of `items`.
+///
+/// The calling thread works through the items along with as many helper threads as are
+/// spare, so that the process never runs more threads than there are cores. Nested calls,
+/// e.g. the rows of a part solved while the days run concurrently, get the helpers left over
+/// and run sequentially once there are none. Threads running out of items hand their core
+/// back straight away, for the nested calls of the items still running.
+pub fn map<T: Sync, R: Send>(items: &[T], f: impl Fn(&T) -> R + Sync) -> Vec<R> {
+    map_within(spare(), items, f)
+}
+
+/// `map` taking its helpers from `spare`.
+fn map_within<T: Sync, R: Send>(
+    spare: &AtomicUsize,
+    items: &[T],
+    f: impl Fn(&T) -> R + Sync,
+) -> Vec<R> {
+    let helpers = reserve(spare, items.len().saturating_sub(1));
+    if helpers == 0 {
+        return items.iter().map(f).collect();
+    }
+
+    let next = AtomicUsize::new(0);
+    let working = AtomicUsize::new(helpers + 1);
+    let work = || {
+        let _worker = Worker {
+            spare,
+            working: &working,
+        };
+        let mut done = vec![];
+        loop {
+            let idx = next.fetch_add(1, Ordering::Relaxed);
+            let Some(item) = items.get(idx) else {
+                return done;
+            };
+            done.push((idx, f(item)));
+        }
+    };
+    let mut results: Vec<Option<R>> = items.iter().map(|_| None).collect();
+
+    thread::scope(|scope| {
+        let handles: Vec<_> = (0..helpers).map(|_| scope.spawn(work)).collect();
+        let mut done = work();
+
+        for handle in handles {
+            done.extend(handle.join().unwrap_or_else(|e| panic::resume_unwind(e)));
+        }
+        for (idx, result) in done {
+            results[idx] = Some(result);
+        }
+    });
+
+    results
+        .into_iter()
+        .map(|result| result.expect("every item is mapped"))
+        .collect()
+}
+
+/// The threads that can run besides the ones already working, one per core but the main one.
+fn spare() -> &'static AtomicUsize {
+    static SPARE: OnceLock<AtomicUsize> = OnceLock::new();
+
+    SPARE.get_or_init(|| {
+        let cores = thread::available_parallelism().map_or(1, |n| n.get());
+        AtomicUsize::new(cores - 1)
+    })
+}
+
+/// Takes up to `wanted` helper threads from `spare`.
+fn reserve(spare: &AtomicUsize, wanted: usize) -> usize {
+    let mut count = 0;
+    let _ = spare.fetch_update(Ordering::AcqRel, Ordering::Acquire, |available| {
+        count = available.min(wanted);
+        Some(available - count)
+    });
+    count
+}
+
+/// A thread working through the items of a `map`, which gives its core back to `spare` once
+/// it runs out of items, or panics. The last one to do so keeps it for the caller.
+struct Worker<'a> {
+    spare: &'a AtomicUsize,
+    working: &'a AtomicUsize,
+}
+
+impl Drop for Worker<'_> {
+    fn drop(&mut self) {
+        if self.working.fetch_sub(1, Ordering::AcqRel) > 1 {
+            self.spare.fetch_add(1, Ordering::AcqRel);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::HashSet;
+    use std::sync::Mutex;
+    use std::time::Duration;
+
+    #[test]
+    fn test_map() {
+        let items: Vec<u64> = (0..50).collect();
+        let squares = map(&items, |n| {
+            thread::sleep(Duration::from_micros(50 - n));
+            n * n
+        });
+
+        assert_eq!(squares, items.iter().map(|n| n * n).collect::<Vec<_>>());
+        assert!(map(&[] as &[u64], |n| *n).is_empty());
+    }
+
+    #[test]
+    fn test_nested_map() {
+        let cores = thread::available_parallelism().map_or(1, |n| n.get());
+        let (running, most) = (AtomicUsize::new(0), AtomicUsize::new(0));
+        let items: Vec<u64> = (0..8).collect();
+
+        let sums = map(&items, |a| {
+            map(&items, |b| {
+                let now = running.fetch_add(1, Ordering::SeqCst) + 1;
+                most.fetch_max(now, Ordering::SeqCst);
+                thread::sleep(Duration::from_micros(200));
+                running.fetch_sub(1, Ordering::SeqCst);
+                a * b
+            })
+            .iter()
+            .sum::<u64>()
+        });
+
+        assert_eq!(sums, items.iter().map(|a| a * 28).collect::<Vec<_>>());
+        assert!(most.load(Ordering::SeqCst) <= cores);
+    }
+
+    #[test]
+    fn test_helpers_given_back() {
+        let spare = AtomicUsize::new(1);
+        let items: Vec<u64> = (0..2).collect();
+
+        let threads = map_within(&spare, &items, |item| {
+            if *item == 0 {
+                return 0;
+            }
+            // The sibling runs out of items meanwhile and gives its core back.
+            thread::sleep(Duration::from_millis(50));
+            let ids = Mutex::new(HashSet::new());
+            map_within(&spare, &items, |_| {
+                ids.lock().unwrap().insert(thread::current().id());
+                thread::sleep(Duration::from_millis(20));
+            });
+            ids.into_inner().unwrap().len()
+        });
+
+        assert_eq!(threads, [0, 2]);
+        assert_eq!(spare.load(Ordering::SeqCst), 1);
+    }
+}
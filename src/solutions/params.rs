@@ -0,0 +1,136 @@
+use crate::solutions::error::SolveError;
+use std::fmt;
+use std::str::FromStr;
+
+/// A single `key=value` override, as given to `--param`.
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub struct Param {
+    pub key: String,
+    pub value: String,
+}
+
+/// The raw parameter overrides of a run, turned into a day's typed parameters by `Params::from_params`.
+#[derive(Clone, Default, PartialEq, Eq, Debug)]
+pub struct PuzzleParams(Vec<Param>);
+
+/// The typed parameters of a day, whose `Default` holds the values of the real puzzle.
+pub trait Params: Default {
+    const KEYS: &'static [&'static str];
+
+    /// Overrides a single parameter, `key` is always one of `KEYS`.
+    fn set(&mut self, key: &str, value: &str) -> Result<(), SolveError>;
+
+    fn from_params(params: &PuzzleParams) -> Result<Self, SolveError> {
+        let mut typed = Self::default();
+
+        for Param { key, value } in &params.0 {
+            if !Self::KEYS.contains(&key.as_str()) {
+                return Err(SolveError::param(match Self::KEYS {
+                    [] => format!("unknown parameter '{key}', this day has no parameters"),
+                    keys => format!("unknown parameter '{key}', expected {}", keys.join(", ")),
+                }));
+            }
+            typed.set(key, value)?;
+        }
+
+        Ok(typed)
+    }
+}
+
+/// The parameters of a day that has none, which rejects any override.
+#[derive(Default)]
+pub struct NoParams;
+
+impl Params for NoParams {
+    const KEYS: &'static [&'static str] = &[];
+
+    fn set(&mut self, _key: &str, _value: &str) -> Result<(), SolveError> {
+        unreachable!("NoParams has no keys")
+    }
+}
+
+#[cfg(test)]
+impl PuzzleParams {
+    pub fn with(mut self, key: &str, value: &str) -> Self {
+        self.0.push(Param {
+            key: key.to_string(),
+            value: value.to_string(),
+        });
+        self
+    }
+}
+
+impl From<Vec<Param>> for PuzzleParams {
+    fn from(params: Vec<Param>) -> Self {
+        Self(params)
+    }
+}
+
+/// Parses the value of a parameter, naming it in the error.
+pub fn parse_value<T: FromStr>(key: &str, value: &str) -> Result<T, SolveError> {
+    value
+        .parse()
+        .map_err(|_| SolveError::param(format!("invalid value '{value}' for parameter '{key}'")))
+}
+
+impl FromStr for Param {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.split_once('=') {
+            Some((key, value)) if !key.trim().is_empty() => Ok(Param {
+                key: key.trim().to_string(),
+                value: value.trim().to_string(),
+            }),
+            _ => Err(format!("invalid parameter '{s}', expected key=value")),
+        }
+    }
+}
+
+impl fmt::Display for Param {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}={}", self.key, self.value)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[derive(Default)]
+    struct Moves {
+        moves: Option<u8>,
+    }
+
+    impl Params for Moves {
+        const KEYS: &'static [&'static str] = &["moves"];
+
+        fn set(&mut self, key: &str, value: &str) -> Result<(), SolveError> {
+            self.moves = Some(parse_value(key, value)?);
+            Ok(())
+        }
+    }
+
+    #[test]
+    fn test_params() {
+        let param: Param = " moves = 3".parse().unwrap();
+        assert_eq!(param.to_string(), "moves=3");
+        assert!("=3".parse::<Param>().is_err());
+
+        let params = PuzzleParams::from(vec![param]);
+        assert_eq!(Moves::from_params(&params).unwrap().moves, Some(3));
+        assert_eq!(
+            Moves::from_params(&PuzzleParams::default()).unwrap().moves,
+            None
+        );
+
+        let unknown = PuzzleParams::default().with("points", "8");
+        assert_eq!(
+            Moves::from_params(&unknown).err().unwrap().to_string(),
+            "unknown parameter 'points', expected moves"
+        );
+        let invalid = PuzzleParams::default().with("moves", "many");
+        assert!(Moves::from_params(&invalid).is_err());
+        assert!(NoParams::from_params(&invalid).is_err());
+    }
+}
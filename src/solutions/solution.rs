@@ -1,27 +1,208 @@
+use std::cell::Cell;
 use std::fmt;
+use std::str::FromStr;
+use std::time::{Duration, Instant};
 
+pub use crate::solutions::answer::Answer;
+pub use crate::solutions::error::SolveError;
+use crate::solutions::grid::Grid;
+use crate::solutions::parallel;
+pub use crate::solutions::params::PuzzleParams;
+use crate::solutions::params::{NoParams, Params};
+
+#[derive(Default)]
 pub struct Solution {
-    pub part1: String,
-    pub part2: String,
-    pub part3: String,
+    pub part1: Option<Answer>,
+    pub part2: Option<Answer>,
+    pub part3: Option<Answer>,
+    pub elapsed: Vec<(Part, Duration)>,
 }
 
+#[derive(Default)]
 pub struct Input {
-    pub part1: String,
-    pub part2: String,
-    pub part3: String,
+    pub part1: Option<String>,
+    pub part2: Option<String>,
+    pub part3: Option<String>,
+}
+
+/// A picture of a part, as produced by `Solver::render`.
+pub enum Image {
+    /// A bitmap of levels between 0 and `max`, e.g. iteration counts.
+    Levels { levels: Grid<u8>, max: u8 },
+    /// A vector drawing, as a complete SVG document.
+    Svg(String),
+}
+
+#[derive(Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Debug)]
+pub enum Part {
+    One,
+    Two,
+    Three,
+}
+
+impl Part {
+    pub const ALL: [Part; 3] = [Part::One, Part::Two, Part::Three];
+
+    pub fn number(&self) -> u8 {
+        match self {
+            Part::One => 1,
+            Part::Two => 2,
+            Part::Three => 3,
+        }
+    }
+}
+
+/// Solvers are shared between the threads running the parts and days concurrently.
+pub trait Solver: Send + Sync {
+    fn part1(&self, input: &str, params: &PuzzleParams) -> Result<Answer, SolveError>;
+
+    fn part2(&self, _input: &str, _params: &PuzzleParams) -> Result<Answer, SolveError> {
+        Ok(Answer::Unsolved)
+    }
+
+    fn part3(&self, _input: &str, _params: &PuzzleParams) -> Result<Answer, SolveError> {
+        Ok(Answer::Unsolved)
+    }
+
+    /// Validates the parameter overrides of a part before it runs, days without parameters reject them all.
+    fn check_params(&self, _part: Part, params: &PuzzleParams) -> Result<(), SolveError> {
+        NoParams::from_params(params).map(|_| ())
+    }
+
+    /// Draws a part for debugging, `None` when the part has nothing to show.
+    fn render(
+        &self,
+        _part: Part,
+        _input: &str,
+        _params: &PuzzleParams,
+    ) -> Result<Option<Image>, SolveError> {
+        Ok(None)
+    }
+
+    /// Runs a single part, resolving the location of parse errors within `input`.
+    fn part(&self, part: Part, input: &str, params: &PuzzleParams) -> Result<Answer, SolveError> {
+        match part {
+            Part::One => self.part1(input, params),
+            Part::Two => self.part2(input, params),
+            Part::Three => self.part3(input, params),
+        }
+        .map_err(|e| e.locate(input))
+    }
+
+    /// Solves every part that has an input concurrently, leaving the others unsolved.
+    fn solve(&self, input: &Input, params: &PuzzleParams) -> Result<Solution, SolveError> {
+        let parts: Vec<(Part, &String)> = Part::ALL
+            .into_iter()
+            .filter_map(|part| Some((part, input.get(part)?)))
+            .collect();
+        for (part, _) in &parts {
+            self.check_params(*part, params)?;
+        }
+        let results = parallel::map(&parts, |(part, text)| {
+            let now = Instant::now();
+            (self.part(*part, text, params), now.elapsed())
+        });
+
+        let mut solution = Solution::default();
+        for ((part, _), (answer, elapsed)) in parts.iter().zip(results) {
+            solution.set(*part, answer?);
+            solution.elapsed.push((*part, elapsed));
+        }
+
+        Ok(solution)
+    }
+}
+
+thread_local! {
+    /// When the part running on this thread was done parsing its input, see `parsed`.
+    static PARSED: Cell<Option<Instant>> = const { Cell::new(None) };
+}
+
+/// Hands back the parsed input of a part, noting that solving starts from there so that
+/// benchmarks can tell parse time from solve time within the same run.
+pub fn parsed<T>(value: T) -> T {
+    PARSED.set(Some(Instant::now()));
+    value
 }
 
-pub trait Solver {
-    fn solve(&self, input: Input) -> Solution;
+/// When the last part run on this thread handed back its parsed input, and forgets it.
+pub fn take_parsed() -> Option<Instant> {
+    PARSED.take()
+}
+
+impl Input {
+    pub fn get(&self, part: Part) -> Option<&String> {
+        match part {
+            Part::One => self.part1.as_ref(),
+            Part::Two => self.part2.as_ref(),
+            Part::Three => self.part3.as_ref(),
+        }
+    }
+
+    pub fn set(&mut self, part: Part, text: String) {
+        match part {
+            Part::One => self.part1 = Some(text),
+            Part::Two => self.part2 = Some(text),
+            Part::Three => self.part3 = Some(text),
+        }
+    }
+}
+
+impl Solution {
+    pub fn get(&self, part: Part) -> Option<&Answer> {
+        match part {
+            Part::One => self.part1.as_ref(),
+            Part::Two => self.part2.as_ref(),
+            Part::Three => self.part3.as_ref(),
+        }
+    }
+
+    pub fn set(&mut self, part: Part, answer: Answer) {
+        match part {
+            Part::One => self.part1 = Some(answer),
+            Part::Two => self.part2 = Some(answer),
+            Part::Three => self.part3 = Some(answer),
+        }
+    }
+
+    pub fn elapsed(&self, part: Part) -> Option<Duration> {
+        self.elapsed
+            .iter()
+            .find(|(p, _)| *p == part)
+            .map(|(_, elapsed)| *elapsed)
+    }
+
+    pub fn total_elapsed(&self) -> Duration {
+        self.elapsed.iter().map(|(_, elapsed)| *elapsed).sum()
+    }
 }
 
 impl fmt::Display for Solution {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
-        write!(
-            f,
-            "Part1: {}, Part2: {}, Part3: {}",
-            self.part1, self.part2, self.part3
-        )
+        let answers: Vec<String> = Part::ALL
+            .iter()
+            .filter_map(|part| self.get(*part).map(|answer| format!("{part}: {answer}")))
+            .collect();
+
+        write!(f, "{}", answers.join(", "))
+    }
+}
+
+impl fmt::Display for Part {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "Part{}", self.number())
+    }
+}
+
+impl FromStr for Part {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "1" => Ok(Part::One),
+            "2" => Ok(Part::Two),
+            "3" => Ok(Part::Three),
+            _ => Err(format!("invalid part '{s}', expected 1, 2 or 3")),
+        }
     }
 }
@@ -0,0 +1,16 @@
+// Helpers shared by the tests of the solutions.
+
+/// A linear congruential generator, for randomized tests that are the same on every run.
+pub struct Lcg(u32);
+
+impl Lcg {
+    pub fn new(seed: u32) -> Self {
+        Self(seed)
+    }
+
+    /// A number in `0..bound`.
+    pub fn below(&mut self, bound: usize) -> usize {
+        self.0 = self.0.wrapping_mul(1_103_515_245).wrapping_add(12_345);
+        (self.0 >> 16) as usize % bound
+    }
+}
@@ -0,0 +1,205 @@
+use crate::answers::{AnswerStore, DayAnswers};
+use crate::fetch::{SITE, Transport};
+use crate::solutions::solution::{Answer, Part, SolveError};
+use argh::FromArgs;
+use serde::Deserialize;
+use std::fmt;
+
+/// The seconds to wait after a wrong answer before submitting the same part again.
+pub const COOLDOWN: u64 = 60;
+
+#[derive(FromArgs)]
+#[argh(subcommand, name = "submit")]
+/// Solves a part and submits its answer, needs $EC_SESSION
+pub struct SubmitArgs {
+    /// the day to submit
+    #[argh(option)]
+    pub day: u8,
+
+    /// the part to submit (1, 2 or 3)
+    #[argh(option)]
+    pub part: Part,
+}
+
+/// Posts answers to the site.
+pub struct Submitter {
+    transport: Box<dyn Transport>,
+    event: u16,
+}
+
+/// The reply of the site to an answer, the hints are only meaningful for wrong answers.
+#[derive(Deserialize, PartialEq, Eq, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct Outcome {
+    pub correct: bool,
+    #[serde(default)]
+    pub length_correct: bool,
+    #[serde(default)]
+    pub first_correct: bool,
+}
+
+impl Submitter {
+    pub fn new(transport: Box<dyn Transport>, event: u16) -> Self {
+        Self { transport, event }
+    }
+
+    /// Submits `answer` unless the store forbids it, then records the verdict in the store.
+    pub fn submit(
+        &self,
+        store: &AnswerStore,
+        day: u8,
+        part: Part,
+        answer: &Answer,
+        now: u64,
+    ) -> Result<Outcome, SolveError> {
+        let mut answers = store.load(day)?;
+        guard(&answers, part, answer, now)?;
+
+        let url = format!(
+            "{SITE}/api/event/{}/quest/{day}/part/{}/answer",
+            self.event,
+            part.number()
+        );
+        let body = serde_json::json!({ "answer": answer.to_string() }).to_string();
+        let response = self.transport.post(&url, &body)?;
+        let outcome: Outcome = serde_json::from_str(&response)
+            .map_err(|e| SolveError::submit(format!("unexpected response from {url}: {e}")))?;
+
+        let recorded = answers.get_mut(part);
+        match outcome.correct {
+            true => recorded.answer = Some(answer.clone()),
+            false => {
+                recorded.wrong.push(answer.clone());
+                recorded.wrong_at = Some(now);
+            }
+        }
+        store.save(day, &answers)?;
+
+        Ok(outcome)
+    }
+}
+
+/// Refuses solved parts, answers already rejected, and any answer during the cooldown.
+fn guard(answers: &DayAnswers, part: Part, answer: &Answer, now: u64) -> Result<(), SolveError> {
+    if *answer == Answer::Unsolved {
+        return Err(SolveError::submit(format!("{part} has no solver yet")));
+    }
+    let Some(recorded) = answers.get(part) else {
+        return Ok(());
+    };
+
+    if let Some(accepted) = &recorded.answer {
+        return Err(SolveError::submit(format!(
+            "{part} is already solved with {accepted}"
+        )));
+    }
+    if recorded.wrong.contains(answer) {
+        return Err(SolveError::submit(format!(
+            "{answer} was already rejected for {part}"
+        )));
+    }
+    if let Some(wrong_at) = recorded.wrong_at {
+        let wait = (wrong_at + COOLDOWN).saturating_sub(now);
+        if wait > 0 {
+            return Err(SolveError::submit(format!(
+                "wait {wait}s after the last wrong answer"
+            )));
+        }
+    }
+
+    Ok(())
+}
+
+impl fmt::Display for Outcome {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        if self.correct {
+            return write!(f, "correct");
+        }
+
+        let hint = |right| if right { "right" } else { "wrong" };
+        write!(
+            f,
+            "wrong (length is {}, first character is {})",
+            hint(self.length_correct),
+            hint(self.first_correct)
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::fetch::tests::MemoryTransport;
+    use std::collections::HashMap;
+    use std::env;
+    use std::fs;
+
+    #[test]
+    fn test_submit() {
+        let root = env::temp_dir().join(format!("ec-submit-{}", std::process::id()));
+        let store = AnswerStore::new(Some(root.clone()));
+        let url = format!("{SITE}/api/event/2025/quest/5/part/2/answer");
+        let transport = MemoryTransport {
+            responses: HashMap::from([
+                (
+                    format!(r#"{url} {{"answer":"41"}}"#),
+                    r#"{"correct":false,"lengthCorrect":true,"firstCorrect":true}"#.into(),
+                ),
+                (
+                    format!(r#"{url} {{"answer":"42"}}"#),
+                    r#"{"correct":true,"lengthCorrect":true,"firstCorrect":true}"#.into(),
+                ),
+            ]),
+            ..Default::default()
+        };
+        let requests = transport.requests.clone();
+        let submitter = Submitter::new(Box::new(transport), 2025);
+        let submit = |answer: u32, now| submitter.submit(&store, 5, Part::Two, &answer.into(), now);
+
+        let wrong = submit(41, 1000).unwrap();
+        let duplicate = submit(41, 2000).unwrap_err().to_string();
+        let cooling = submit(42, 1030).unwrap_err().to_string();
+        let correct = submit(42, 1060).unwrap();
+        let solved = submit(43, 1200).unwrap_err().to_string();
+        let answers = store.load(5).unwrap();
+        fs::remove_dir_all(root).unwrap();
+
+        assert!(!wrong.correct);
+        assert_eq!(
+            duplicate,
+            "not submitted: 41 was already rejected for Part2"
+        );
+        assert_eq!(
+            cooling,
+            "not submitted: wait 30s after the last wrong answer"
+        );
+        assert!(correct.correct);
+        assert_eq!(solved, "not submitted: Part2 is already solved with 42");
+        assert_eq!(requests.get(), 2);
+        assert_eq!(answers.accepted(Part::Two), Some(&Answer::Integer(42)));
+        assert_eq!(
+            answers.get(Part::Two).unwrap().wrong,
+            vec![Answer::Integer(41)]
+        );
+    }
+
+    #[test]
+    fn test_submit_error() {
+        let root = env::temp_dir().join(format!("ec-submit-error-{}", std::process::id()));
+        let store = AnswerStore::new(Some(root.clone()));
+        let submitter = Submitter::new(Box::new(MemoryTransport::default()), 2025);
+
+        let error = submitter
+            .submit(&store, 5, Part::Two, &42u32.into(), 1000)
+            .unwrap_err()
+            .to_string();
+        let answers = store.load(5).unwrap();
+        let _ = fs::remove_dir_all(root);
+
+        assert_eq!(
+            error,
+            format!("not submitted: POST {SITE}/api/event/2025/quest/5/part/2/answer failed: 404")
+        );
+        assert!(answers.get(Part::Two).is_none());
+    }
+}